@@ -0,0 +1,330 @@
+//! Cluster federation: channels can be owned by different server nodes
+//! while subscribers on any node still receive live updates.
+//!
+//! [`ClusterMetadata`] is a read-only channel-ID -> owning-node allocation,
+//! consulted by `channel-notify`/`channel-subscribe` to decide whether a
+//! request should be served locally or forwarded. [`Broadcasting`] is the
+//! owning node's registry of which remote nodes currently have a local
+//! subscriber for a channel, so `channel-notify` knows who else to forward
+//! a persisted message to. [`ClusterApi`] is the node-to-node transport;
+//! [`ClusterClient`] is the real HTTP implementation. With no channel
+//! owners configured, every channel resolves as locally owned — the
+//! existing single-node behavior is the default.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use serde_json::Value;
+
+/// Read-only channel-ID -> owning-node allocation.
+#[derive(Debug, Clone, Default)]
+pub struct ClusterMetadata {
+    /// This process's own node ID.
+    pub node_id: String,
+    channel_owners: HashMap<String, String>,
+}
+
+impl ClusterMetadata {
+    pub fn new(node_id: impl Into<String>, channel_owners: HashMap<String, String>) -> Self {
+        Self {
+            node_id: node_id.into(),
+            channel_owners,
+        }
+    }
+
+    /// The node ID that owns `channel_id`, or `None` if unconfigured (in
+    /// which case the channel is treated as locally owned).
+    pub fn owner_of(&self, channel_id: &str) -> Option<&str> {
+        self.channel_owners.get(channel_id).map(String::as_str)
+    }
+
+    /// True if `channel_id` is explicitly owned by a *different* node.
+    pub fn is_remote(&self, channel_id: &str) -> bool {
+        matches!(self.owner_of(channel_id), Some(owner) if owner != self.node_id)
+    }
+}
+
+/// Per-channel registry of remote nodes with a live local subscriber, kept
+/// by the owning node so `channel-notify` can forward persisted messages
+/// onward after handling them locally.
+#[derive(Default)]
+pub struct Broadcasting {
+    subscribers: DashMap<String, HashSet<String>>,
+}
+
+impl Broadcasting {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `node_id` has a local subscriber for `channel_id`.
+    pub fn register(&self, channel_id: &str, node_id: &str) {
+        self.subscribers
+            .entry(channel_id.to_string())
+            .or_default()
+            .insert(node_id.to_string());
+    }
+
+    /// The remote nodes currently registered for `channel_id`.
+    pub fn subscriber_nodes(&self, channel_id: &str) -> Vec<String> {
+        self.subscribers
+            .get(channel_id)
+            .map(|nodes| nodes.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClusterError {
+    #[error("unknown cluster node: {0}")]
+    UnknownNode(String),
+    #[error("cluster request to {0} failed: {1}")]
+    Request(String, String),
+}
+
+/// Node-to-node transport for cluster federation.
+#[async_trait]
+pub trait ClusterApi: Send + Sync {
+    /// Forward a `channel-notify` call to the node that owns `channel_id`,
+    /// for it to persist and fan out via the same path it uses locally.
+    async fn forward_notify(
+        &self,
+        node_id: &str,
+        channel_id: &str,
+        sender: &str,
+        message: &str,
+        fallback: bool,
+    ) -> Result<(), ClusterError>;
+
+    /// Push an already-persisted event out to a node with a local
+    /// subscriber, for it to publish on its own `channel_streams` registry
+    /// (no further persistence or forwarding on the receiving end).
+    async fn forward_publish(
+        &self,
+        node_id: &str,
+        channel_id: &str,
+        event: &Value,
+    ) -> Result<(), ClusterError>;
+
+    /// Tell the owning node that `subscriber_node_id` has a local
+    /// subscriber for `channel_id` and should receive future publishes.
+    async fn register_subscription(
+        &self,
+        node_id: &str,
+        channel_id: &str,
+        subscriber_node_id: &str,
+    ) -> Result<(), ClusterError>;
+}
+
+/// `true` if `authorization_header` is exactly `Bearer <configured_secret>`,
+/// compared in constant time — a plain `==` on a shared secret used to
+/// authenticate inter-node requests lets an attacker recover it
+/// byte-by-byte via a timing side-channel. Shared by both the axum
+/// (`cluster_routes`) and Lambda (`lambda`) receiving ends so the check
+/// isn't maintained twice. An empty `configured_secret` always rejects,
+/// so an unconfigured node isn't an open relay by default.
+pub fn shared_secret_authorized(configured_secret: &str, authorization_header: Option<&str>) -> bool {
+    if configured_secret.is_empty() {
+        return false;
+    }
+    let expected = format!("Bearer {}", configured_secret);
+    match authorization_header {
+        Some(actual) => ring::constant_time::verify_slices_are_equal(actual.as_bytes(), expected.as_bytes()).is_ok(),
+        None => false,
+    }
+}
+
+/// Real node-to-node [`ClusterApi`], authenticated with a shared secret
+/// bearer token over HTTP.
+pub struct ClusterClient {
+    http: reqwest::Client,
+    /// node ID -> base URL (e.g. `https://node-b.internal:8080`).
+    node_addresses: HashMap<String, String>,
+    shared_secret: String,
+}
+
+impl ClusterClient {
+    pub fn new(node_addresses: HashMap<String, String>, shared_secret: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            node_addresses,
+            shared_secret: shared_secret.into(),
+        }
+    }
+
+    fn address_of(&self, node_id: &str) -> Result<&str, ClusterError> {
+        self.node_addresses
+            .get(node_id)
+            .map(String::as_str)
+            .ok_or_else(|| ClusterError::UnknownNode(node_id.to_string()))
+    }
+
+    async fn post(&self, node_id: &str, path: &str, body: Value) -> Result<(), ClusterError> {
+        let addr = self.address_of(node_id)?;
+        let resp = self
+            .http
+            .post(format!("{}{}", addr, path))
+            .bearer_auth(&self.shared_secret)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| ClusterError::Request(node_id.to_string(), e.to_string()))?;
+
+        if !resp.status().is_success() {
+            return Err(ClusterError::Request(
+                node_id.to_string(),
+                format!("node responded with {}", resp.status()),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ClusterApi for ClusterClient {
+    async fn forward_notify(
+        &self,
+        node_id: &str,
+        channel_id: &str,
+        sender: &str,
+        message: &str,
+        fallback: bool,
+    ) -> Result<(), ClusterError> {
+        self.post(
+            node_id,
+            "/cluster/notify",
+            serde_json::json!({"channel": channel_id, "sender": sender, "message": message, "fallback": fallback}),
+        )
+        .await
+    }
+
+    async fn forward_publish(&self, node_id: &str, channel_id: &str, event: &Value) -> Result<(), ClusterError> {
+        self.post(
+            node_id,
+            "/cluster/publish",
+            serde_json::json!({"channel": channel_id, "event": event}),
+        )
+        .await
+    }
+
+    async fn register_subscription(
+        &self,
+        node_id: &str,
+        channel_id: &str,
+        subscriber_node_id: &str,
+    ) -> Result<(), ClusterError> {
+        self.post(
+            node_id,
+            "/cluster/subscribe",
+            serde_json::json!({"channel": channel_id, "nodeId": subscriber_node_id}),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    pub struct MockCluster {
+        pub notifies: Mutex<Vec<(String, String, String, String)>>,
+        pub publishes: Mutex<Vec<(String, String, Value)>>,
+        pub registrations: Mutex<Vec<(String, String, String)>>,
+    }
+
+    impl MockCluster {
+        pub fn new() -> Self {
+            Self::default()
+        }
+    }
+
+    #[async_trait]
+    impl ClusterApi for MockCluster {
+        async fn forward_notify(
+            &self,
+            node_id: &str,
+            channel_id: &str,
+            sender: &str,
+            message: &str,
+            _fallback: bool,
+        ) -> Result<(), ClusterError> {
+            self.notifies.lock().unwrap().push((
+                node_id.into(),
+                channel_id.into(),
+                sender.into(),
+                message.into(),
+            ));
+            Ok(())
+        }
+
+        async fn forward_publish(&self, node_id: &str, channel_id: &str, event: &Value) -> Result<(), ClusterError> {
+            self.publishes
+                .lock()
+                .unwrap()
+                .push((node_id.into(), channel_id.into(), event.clone()));
+            Ok(())
+        }
+
+        async fn register_subscription(
+            &self,
+            node_id: &str,
+            channel_id: &str,
+            subscriber_node_id: &str,
+        ) -> Result<(), ClusterError> {
+            self.registrations.lock().unwrap().push((
+                node_id.into(),
+                channel_id.into(),
+                subscriber_node_id.into(),
+            ));
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_owner_of_unconfigured_is_local() {
+        let meta = ClusterMetadata::new("node-a", HashMap::new());
+        assert_eq!(meta.owner_of("ch1"), None);
+        assert!(!meta.is_remote("ch1"));
+    }
+
+    #[test]
+    fn test_is_remote_for_other_node() {
+        let mut owners = HashMap::new();
+        owners.insert("ch1".to_string(), "node-b".to_string());
+        let meta = ClusterMetadata::new("node-a", owners);
+        assert!(meta.is_remote("ch1"));
+    }
+
+    #[test]
+    fn test_shared_secret_authorized_requires_matching_bearer_token() {
+        assert!(shared_secret_authorized("s3cret", Some("Bearer s3cret")));
+        assert!(!shared_secret_authorized("s3cret", Some("Bearer wrong")));
+        assert!(!shared_secret_authorized("s3cret", None));
+    }
+
+    #[test]
+    fn test_shared_secret_authorized_rejects_when_unconfigured() {
+        assert!(!shared_secret_authorized("", Some("Bearer anything")));
+        assert!(!shared_secret_authorized("", None));
+    }
+
+    #[test]
+    fn test_broadcasting_tracks_subscriber_nodes() {
+        let reg = Broadcasting::new();
+        reg.register("ch1", "node-b");
+        reg.register("ch1", "node-c");
+        let mut nodes = reg.subscriber_nodes("ch1");
+        nodes.sort();
+        assert_eq!(nodes, vec!["node-b".to_string(), "node-c".to_string()]);
+        assert!(reg.subscriber_nodes("ch2").is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+//! Node-to-node HTTP endpoints for cluster federation — the receiving side
+//! of [`crate::cluster::ClusterApi`]. Mounted alongside the MCP router in
+//! `main` for the axum (non-Lambda) build; the Lambda handler matches these
+//! paths directly since it doesn't delegate to an axum `Router`.
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::tools::channel_msg;
+use crate::tools::channel_stream::ChannelEvent;
+use crate::tools::Deps;
+
+/// Build the `/cluster/*` router, to be merged into the main axum `Router`.
+pub fn router(deps: Arc<Deps>) -> Router {
+    Router::new()
+        .route("/cluster/notify", post(handle_notify))
+        .route("/cluster/publish", post(handle_publish))
+        .route("/cluster/subscribe", post(handle_subscribe))
+        .with_state(deps)
+}
+
+/// An unconfigured `cluster_shared_secret` rejects every request, so an
+/// unconfigured node isn't an open relay by default.
+fn authorized(deps: &Deps, headers: &HeaderMap) -> bool {
+    crate::cluster::shared_secret_authorized(
+        &deps.cluster_shared_secret,
+        headers.get("authorization").and_then(|v| v.to_str().ok()),
+    )
+}
+
+#[derive(Deserialize)]
+pub struct NotifyBody {
+    pub channel: String,
+    pub sender: String,
+    pub message: String,
+    #[serde(default)]
+    pub fallback: bool,
+}
+
+pub async fn handle_notify(
+    State(deps): State<Arc<Deps>>,
+    headers: HeaderMap,
+    Json(body): Json<NotifyBody>,
+) -> StatusCode {
+    if !authorized(&deps, &headers) {
+        return StatusCode::UNAUTHORIZED;
+    }
+    match channel_msg::persist_and_fan_out(&deps, &body.channel, &body.sender, &body.message, body.fallback).await {
+        Ok(_) => StatusCode::OK,
+        Err(e) => {
+            tracing::error!("cluster/notify: {:?}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct PublishBody {
+    pub channel: String,
+    pub event: Value,
+}
+
+pub async fn handle_publish(
+    State(deps): State<Arc<Deps>>,
+    headers: HeaderMap,
+    Json(body): Json<PublishBody>,
+) -> StatusCode {
+    if !authorized(&deps, &headers) {
+        return StatusCode::UNAUTHORIZED;
+    }
+    match serde_json::from_value::<ChannelEvent>(body.event) {
+        Ok(event) => {
+            deps.channel_streams.publish(&body.channel, event);
+            StatusCode::OK
+        }
+        Err(e) => {
+            tracing::error!("cluster/publish: invalid event: {}", e);
+            StatusCode::BAD_REQUEST
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct SubscribeBody {
+    pub channel: String,
+    #[serde(rename = "nodeId")]
+    pub node_id: String,
+}
+
+pub async fn handle_subscribe(
+    State(deps): State<Arc<Deps>>,
+    headers: HeaderMap,
+    Json(body): Json<SubscribeBody>,
+) -> StatusCode {
+    if !authorized(&deps, &headers) {
+        return StatusCode::UNAUTHORIZED;
+    }
+    deps.broadcasting.register(&body.channel, &body.node_id);
+    StatusCode::OK
+}
@@ -1,19 +1,88 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use lambda_http::{Request, Response, Body, Error, service_fn};
-use mcpserver::{JsonRpcRequest, Server, new_error_response};
+use mcpserver::{JsonRpcRequest, RpcErrorKind, Server};
+
+use crate::notify::sns_inbound::{verify_and_handle, SnsEnvelope};
+use crate::tools::channel_msg;
+use crate::tools::channel_stream::ChannelEvent;
+use crate::tools::Deps;
+
+/// Grace period for in-flight `/mcp` calls and open SSE streams to finish
+/// once a termination signal arrives, before this process exits.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(25);
 
 /// Run the Lambda handler loop.
-pub async fn run(srv: Server) {
+///
+/// Lambda (and container runtimes generally) send SIGTERM ahead of a
+/// freeze/stop, which would otherwise cut off in-flight `/mcp` calls and
+/// open SSE streams with no warning. Once that signal (or SIGINT, for
+/// local runs) arrives, new requests get `503` immediately while existing
+/// ones are given up to [`DRAIN_TIMEOUT`] to finish.
+pub async fn run(srv: Server, deps: Arc<Deps>) {
     let srv = Arc::new(srv);
+    let draining = Arc::new(AtomicBool::new(false));
+    let in_flight = Arc::new(AtomicUsize::new(0));
+
+    tokio::spawn(shutdown_listener(draining.clone(), in_flight.clone()));
+
     let func = service_fn(move |event: Request| {
         let srv = srv.clone();
-        async move { handle(event, &srv).await }
+        let deps = deps.clone();
+        let draining = draining.clone();
+        let in_flight = in_flight.clone();
+        async move {
+            if draining.load(Ordering::SeqCst) {
+                return Ok(Response::builder().status(503).body(Body::Empty).unwrap());
+            }
+            in_flight.fetch_add(1, Ordering::SeqCst);
+            let result = handle(event, &srv, &deps).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            result
+        }
     });
-    lambda_http::run(func).await.unwrap();
+
+    if let Err(e) = lambda_http::run(func).await {
+        tracing::error!(error = %e, "lambda runtime exited with error");
+    }
 }
 
-async fn handle(event: Request, srv: &Server) -> Result<Response<Body>, Error> {
+/// Wait for a termination signal, flip `draining` so new requests are
+/// rejected, then wait for `in_flight` to reach zero (bounded by
+/// [`DRAIN_TIMEOUT`]) before exiting the process.
+async fn shutdown_listener(draining: Arc<AtomicBool>, in_flight: Arc<AtomicUsize>) {
+    wait_for_termination().await;
+    tracing::info!("termination signal received, draining in-flight requests");
+    draining.store(true, Ordering::SeqCst);
+
+    let deadline = tokio::time::Instant::now() + DRAIN_TIMEOUT;
+    while in_flight.load(Ordering::SeqCst) > 0 && tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    if in_flight.load(Ordering::SeqCst) > 0 {
+        tracing::warn!("drain timeout elapsed with requests still in flight");
+    }
+    std::process::exit(0);
+}
+
+#[cfg(unix)]
+async fn wait_for_termination() {
+    use tokio::signal::unix::{signal, SignalKind};
+    let mut sigterm = signal(SignalKind::terminate()).expect("failed to install SIGTERM handler");
+    tokio::select! {
+        _ = sigterm.recv() => {}
+        _ = tokio::signal::ctrl_c() => {}
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_termination() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+async fn handle(event: Request, srv: &Server, deps: &Arc<Deps>) -> Result<Response<Body>, Error> {
     let method = event.method().as_str().to_uppercase();
     let path = event.uri().path();
 
@@ -26,6 +95,10 @@ async fn handle(event: Request, srv: &Server) -> Result<Response<Body>, Error> {
                 .unwrap())
         }
         ("POST", "/mcp") => handle_jsonrpc(event, srv).await,
+        ("POST", "/cluster/notify") => handle_cluster_notify(event, deps).await,
+        ("POST", "/cluster/publish") => handle_cluster_publish(event, deps).await,
+        ("POST", "/cluster/subscribe") => handle_cluster_subscribe(event, deps).await,
+        ("POST", "/sns") => handle_sns(event).await,
         _ => {
             Ok(Response::builder()
                 .status(404)
@@ -36,6 +109,124 @@ async fn handle(event: Request, srv: &Server) -> Result<Response<Body>, Error> {
     }
 }
 
+/// `true` if the request's bearer token matches the configured cluster
+/// shared secret. An unconfigured secret rejects every request, so an
+/// unconfigured node isn't an open relay by default.
+fn cluster_authorized(event: &Request, deps: &Deps) -> bool {
+    crate::cluster::shared_secret_authorized(
+        &deps.cluster_shared_secret,
+        event.headers().get("authorization").and_then(|v| v.to_str().ok()),
+    )
+}
+
+fn event_body(event: &Request) -> String {
+    match event.body() {
+        Body::Text(s) => s.clone(),
+        Body::Binary(b) => String::from_utf8_lossy(b).into_owned(),
+        Body::Empty => String::new(),
+    }
+}
+
+fn plain_status(status: u16) -> Result<Response<Body>, Error> {
+    Ok(Response::builder().status(status).body(Body::Empty).unwrap())
+}
+
+async fn handle_cluster_notify(event: Request, deps: &Deps) -> Result<Response<Body>, Error> {
+    if !cluster_authorized(&event, deps) {
+        return plain_status(401);
+    }
+
+    #[derive(serde::Deserialize)]
+    struct NotifyBody {
+        channel: String,
+        sender: String,
+        message: String,
+        #[serde(default)]
+        fallback: bool,
+    }
+
+    let body: NotifyBody = match serde_json::from_str(&event_body(&event)) {
+        Ok(b) => b,
+        Err(_) => return plain_status(400),
+    };
+
+    match channel_msg::persist_and_fan_out(deps, &body.channel, &body.sender, &body.message, body.fallback).await {
+        Ok(_) => plain_status(200),
+        Err(e) => {
+            tracing::error!("cluster/notify: {:?}", e);
+            plain_status(500)
+        }
+    }
+}
+
+async fn handle_cluster_publish(event: Request, deps: &Deps) -> Result<Response<Body>, Error> {
+    if !cluster_authorized(&event, deps) {
+        return plain_status(401);
+    }
+
+    #[derive(serde::Deserialize)]
+    struct PublishBody {
+        channel: String,
+        event: serde_json::Value,
+    }
+
+    let body: PublishBody = match serde_json::from_str(&event_body(&event)) {
+        Ok(b) => b,
+        Err(_) => return plain_status(400),
+    };
+
+    match serde_json::from_value::<ChannelEvent>(body.event) {
+        Ok(channel_event) => {
+            deps.channel_streams.publish(&body.channel, channel_event);
+            plain_status(200)
+        }
+        Err(e) => {
+            tracing::error!("cluster/publish: invalid event: {}", e);
+            plain_status(400)
+        }
+    }
+}
+
+async fn handle_cluster_subscribe(event: Request, deps: &Deps) -> Result<Response<Body>, Error> {
+    if !cluster_authorized(&event, deps) {
+        return plain_status(401);
+    }
+
+    #[derive(serde::Deserialize)]
+    struct SubscribeBody {
+        channel: String,
+        #[serde(rename = "nodeId")]
+        node_id: String,
+    }
+
+    let body: SubscribeBody = match serde_json::from_str(&event_body(&event)) {
+        Ok(b) => b,
+        Err(_) => return plain_status(400),
+    };
+
+    deps.broadcasting.register(&body.channel, &body.node_id);
+    plain_status(200)
+}
+
+async fn handle_sns(event: Request) -> Result<Response<Body>, Error> {
+    let envelope: SnsEnvelope = match serde_json::from_str(&event_body(&event)) {
+        Ok(e) => e,
+        Err(_) => return plain_status(400),
+    };
+
+    match verify_and_handle(envelope).await {
+        Ok(Some(message)) => {
+            tracing::info!(%message, "received SNS notification");
+            plain_status(200)
+        }
+        Ok(None) => plain_status(200),
+        Err(e) => {
+            tracing::warn!(error = %e, "rejected inbound SNS message");
+            plain_status(400)
+        }
+    }
+}
+
 async fn handle_jsonrpc(event: Request, srv: &Server) -> Result<Response<Body>, Error> {
     let body = match event.body() {
         Body::Text(s) => s.clone(),
@@ -43,14 +234,30 @@ async fn handle_jsonrpc(event: Request, srv: &Server) -> Result<Response<Body>,
         Body::Empty => String::new(),
     };
 
-    let rpc_req: JsonRpcRequest = match serde_json::from_str(&body) {
+    // JSON-RPC 2.0 permits a batch: a JSON array of requests instead of a
+    // single object. Peek at the raw value first so single requests keep
+    // going through the existing path unchanged.
+    let raw: serde_json::Value = match serde_json::from_str(&body) {
+        Ok(v) => v,
+        Err(e) => {
+            let resp = RpcErrorKind::ParseError(format!("invalid JSON: {}", e)).into_response(None);
+            let json = serde_json::to_string(&resp).unwrap();
+            return Ok(Response::builder()
+                .status(400)
+                .header("content-type", "application/json")
+                .body(Body::Text(json))
+                .unwrap());
+        }
+    };
+
+    if let serde_json::Value::Array(items) = raw {
+        return handle_jsonrpc_batch(items, srv).await;
+    }
+
+    let rpc_req: JsonRpcRequest = match serde_json::from_value(raw) {
         Ok(r) => r,
         Err(e) => {
-            let resp = new_error_response(
-                None,
-                -32700, // parse error
-                format!("invalid JSON: {}", e),
-            );
+            let resp = RpcErrorKind::ParseError(format!("invalid JSON: {}", e)).into_response(None);
             let json = serde_json::to_string(&resp).unwrap();
             return Ok(Response::builder()
                 .status(400)
@@ -85,3 +292,56 @@ async fn handle_jsonrpc(event: Request, srv: &Server) -> Result<Response<Body>,
         .body(Body::Text(json))
         .unwrap())
 }
+
+/// Dispatch a JSON-RPC batch: each element runs through `srv.handle`
+/// concurrently, elements that fail to parse get their own `id: null` error
+/// object rather than failing the whole batch, and notifications are
+/// dropped from the collected response array (batch of only notifications
+/// => `202` with no body, matching the single-request notification case).
+async fn handle_jsonrpc_batch(
+    items: Vec<serde_json::Value>,
+    srv: &Server,
+) -> Result<Response<Body>, Error> {
+    if items.is_empty() {
+        let resp = RpcErrorKind::InvalidRequest("empty batch".into()).into_response(None);
+        let json = serde_json::to_string(&resp).unwrap();
+        return Ok(Response::builder()
+            .status(400)
+            .header("content-type", "application/json")
+            .body(Body::Text(json))
+            .unwrap());
+    }
+
+    let calls = items.into_iter().map(|item| async move {
+        match serde_json::from_value::<JsonRpcRequest>(item) {
+            Ok(rpc_req) => {
+                if rpc_req.method.starts_with("notifications/") {
+                    None
+                } else {
+                    Some(srv.handle(rpc_req).await)
+                }
+            }
+            Err(e) => Some(RpcErrorKind::ParseError(format!("invalid JSON: {}", e)).into_response(Some(serde_json::Value::Null))),
+        }
+    });
+
+    let responses: Vec<_> = futures::future::join_all(calls)
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+
+    if responses.is_empty() {
+        return Ok(Response::builder()
+            .status(202)
+            .body(Body::Empty)
+            .unwrap());
+    }
+
+    let json = serde_json::to_string(&responses).unwrap();
+    Ok(Response::builder()
+        .status(200)
+        .header("content-type", "application/json")
+        .body(Body::Text(json))
+        .unwrap())
+}
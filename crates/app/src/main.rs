@@ -1,34 +1,98 @@
 mod auth;
+mod cluster;
+#[cfg(not(any(feature = "lambda", feature = "stream-consumer")))]
+mod cluster_routes;
 mod dynamo;
 mod notify;
+#[cfg(not(any(feature = "lambda", feature = "stream-consumer")))]
+mod sns_routes;
+#[cfg(feature = "stream-consumer")]
+mod stream_consumer;
 mod tools;
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use cluster::{Broadcasting, ClusterApi, ClusterClient, ClusterMetadata};
 use dynamo::{DynamoClient, DynamoApi};
 use notify::sns::{SnsClient, SnsApi};
 use notify::ses::{SesClient, SesApi};
 use notify::webpush::WebPushKeys;
 use tools::Deps;
 
+/// Parse a JSON object env var (e.g. `CLUSTER_CHANNEL_OWNERS`,
+/// `CLUSTER_NODE_ADDRESSES`) into a string map. Missing or malformed input
+/// is treated as empty, which keeps cluster federation off by default.
+fn json_map_env(name: &str) -> HashMap<String, String> {
+    std::env::var(name)
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Build the JWT [`auth::KeySet`] from env. `JWT_SIGNING_KEYS` (a
+/// `{kid: secret}` JSON map) plus `JWT_ACTIVE_KID` supports rotation — add
+/// the incoming key alongside the outgoing one, point `JWT_ACTIVE_KID` at
+/// it, and tokens signed under the outgoing key keep verifying until they
+/// expire. Falls back to a single key from `JWT_SECRET` when
+/// `JWT_SIGNING_KEYS` isn't set, which is the common single-key case.
+fn build_jwt_keys() -> auth::KeySet {
+    let active_kid = std::env::var("JWT_ACTIVE_KID").unwrap_or_else(|_| "default".into());
+    let signing_keys = json_map_env("JWT_SIGNING_KEYS");
+
+    if signing_keys.is_empty() {
+        let secret = std::env::var("JWT_SECRET").unwrap_or_default();
+        return auth::KeySet::single_hs256(active_kid, &secret);
+    }
+
+    let keys = signing_keys
+        .into_iter()
+        .map(|(kid, secret)| auth::SigningKey::hs256(kid, &secret))
+        .collect();
+    auth::KeySet::new(keys, active_kid)
+}
+
 async fn build_deps() -> Result<Arc<Deps>, Box<dyn std::error::Error>> {
     let table_name = std::env::var("TABLE_NAME").unwrap_or_else(|_| "app".into());
-    let db = DynamoClient::new(&table_name).await?;
+    // `AWS_ENDPOINT_URL`/`DYNAMODB_ENDPOINT` point the real `DynamoClient`
+    // at a DynamoDB-Local container instead of resolving AWS's regional
+    // endpoint, so integration tests can exercise the real query/GSI/batch
+    // paths rather than only `mock::MockDynamo`.
+    let dynamo_endpoint = std::env::var("AWS_ENDPOINT_URL")
+        .or_else(|_| std::env::var("DYNAMODB_ENDPOINT"))
+        .ok();
+    let db = match dynamo_endpoint {
+        Some(endpoint) => DynamoClient::with_endpoint(&table_name, &endpoint).await?,
+        None => DynamoClient::new(&table_name).await?,
+    };
 
     let aws_config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
     let sns = SnsClient::new(&aws_config);
     let ses = SesClient::new(&aws_config);
 
+    let node_id = std::env::var("CLUSTER_NODE_ID").unwrap_or_default();
+    let channel_owners = json_map_env("CLUSTER_CHANNEL_OWNERS");
+    let node_addresses = json_map_env("CLUSTER_NODE_ADDRESSES");
+    let cluster_shared_secret = std::env::var("CLUSTER_SHARED_SECRET").unwrap_or_default();
+
     Ok(Arc::new(Deps {
         db: Arc::new(db) as Arc<dyn DynamoApi>,
-        jwt_secret: std::env::var("JWT_SECRET").unwrap_or_default(),
+        jwt_keys: Arc::new(build_jwt_keys()),
         sns: Arc::new(sns) as Arc<dyn SnsApi>,
         ses: Arc::new(ses) as Arc<dyn SesApi>,
         ses_from_email: std::env::var("SES_FROM_EMAIL").unwrap_or_default(),
         web_push_keys: WebPushKeys {
             vapid_public_key: std::env::var("VAPID_PUBLIC_KEY").unwrap_or_default(),
             vapid_private_key: std::env::var("VAPID_PRIVATE_KEY").unwrap_or_default(),
+            vapid_subject: std::env::var("VAPID_SUBJECT").unwrap_or_default(),
         },
+        channel_streams: Arc::new(tools::channel_stream::ChannelStreamRegistry::new()),
+        channel_versions: Arc::new(tools::channel::ChannelChangeRegistry::new()),
+        notifications: Arc::new(mcpserver::NotificationRegistry::new()),
+        cluster_metadata: Arc::new(ClusterMetadata::new(node_id, channel_owners)),
+        cluster: Arc::new(ClusterClient::new(node_addresses, cluster_shared_secret.clone())) as Arc<dyn ClusterApi>,
+        broadcasting: Arc::new(Broadcasting::new()),
+        cluster_shared_secret,
     }))
 }
 
@@ -43,7 +107,7 @@ fn build_server(deps: Arc<Deps>) -> mcpserver::Server {
     srv
 }
 
-#[cfg(not(feature = "lambda"))]
+#[cfg(not(any(feature = "lambda", feature = "stream-consumer")))]
 #[tokio::main]
 async fn main() {
     tracing_subscriber::fmt()
@@ -55,14 +119,22 @@ async fn main() {
         .init();
 
     let deps = build_deps().await.expect("failed to build dependencies");
-    let srv = build_server(deps);
+    let srv = build_server(deps.clone());
 
     let port = std::env::var("PORT").unwrap_or_else(|_| "8080".into());
     let addr = format!("0.0.0.0:{}", port);
 
     tracing::info!(addr = %addr, "starting MCP server");
 
-    let router = mcpserver::http_router(srv);
+    // Channel push notifications (`channel-notify` fan-out, `notifications/
+    // message` frames) only reach a client over the WebSocket transport —
+    // merge it in alongside the request/response HTTP transport so a
+    // `channel-subscribe`'d client actually gets them instead of having to
+    // poll `channel-messages`.
+    let router = mcpserver::http_router(srv.clone())
+        .merge(mcpserver::ws_router(srv, deps.notifications.clone()))
+        .merge(cluster_routes::router(deps))
+        .merge(sns_routes::router());
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
     axum::serve(listener, router).await.unwrap();
 }
@@ -83,8 +155,29 @@ async fn main() {
         .init();
 
     let deps = build_deps().await.expect("failed to build dependencies");
-    let srv = build_server(deps);
+    let srv = build_server(deps.clone());
 
     tracing::info!("starting Lambda handler");
-    lambda::run(srv).await;
+    lambda::run(srv, deps).await;
+}
+
+// A separate Lambda function from `lambda`, above — triggered by the
+// table's DynamoDB Streams ARN rather than by HTTP, so it ships as its
+// own binary/feature rather than another route on the HTTP handler.
+#[cfg(feature = "stream-consumer")]
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "info".into()),
+        )
+        .json()
+        .with_target(false)
+        .init();
+
+    let deps = build_deps().await.expect("failed to build dependencies");
+
+    tracing::info!("starting DynamoDB Streams consumer");
+    stream_consumer::run(deps).await;
 }
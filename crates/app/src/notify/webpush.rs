@@ -1,24 +1,230 @@
-/// VAPID keys for web push.
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use aes_gcm::aead::{Aead, Payload};
+use aes_gcm::{Aes128Gcm, KeyInit, Nonce};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hkdf::Hkdf;
+use p256::ecdh::diffie_hellman;
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature, SigningKey};
+use p256::{PublicKey, SecretKey};
+use rand::RngCore;
+use serde::Deserialize;
+use sha2::Sha256;
+
+/// VAPID keys for web push (RFC 8292), base64url (no padding) encoded: the
+/// public key is the uncompressed P-256 point (65 bytes) and the private key
+/// is the raw scalar (32 bytes). `vapid_subject` is the `mailto:`/`https://`
+/// contact URI sent as the JWT's `sub` claim.
 #[derive(Debug, Clone, Default)]
 pub struct WebPushKeys {
     pub vapid_public_key: String,
     pub vapid_private_key: String,
+    pub vapid_subject: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WebPushError {
+    #[error("invalid subscription: {0}")]
+    InvalidSubscription(String),
+    #[error("invalid vapid keys: {0}")]
+    InvalidKeys(String),
+    #[error("encryption failed: {0}")]
+    Encryption(String),
+    #[error("push request failed: {0}")]
+    Request(String),
+    /// The push service reports the endpoint no longer exists (`404`/`410`)
+    /// — the caller should delete the stored subscription.
+    #[error("subscription gone (HTTP {0})")]
+    Gone(u16),
+    #[error("payload too large for a single aes128gcm record ({0} bytes)")]
+    PayloadTooLarge(usize),
+}
+
+/// RFC 8188 record size used for every message. Fixed rather than sized to
+/// the plaintext so the encrypted body's length doesn't leak the
+/// notification's content length to anyone observing the push service
+/// traffic.
+const RECORD_SIZE: u32 = 4096;
+
+/// Cap on how long a single push POST can hang. `channel-notify`'s fan-out
+/// calls [`send_web_push`] once per stored subscription, sequentially —
+/// without a bound here, one unresponsive push service would stall
+/// delivery to every other subscriber behind it in the loop.
+const PUSH_REQUEST_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Deserialize)]
+struct PushSubscription {
+    endpoint: String,
+    keys: PushSubscriptionKeys,
+}
+
+#[derive(Debug, Deserialize)]
+struct PushSubscriptionKeys {
+    p256dh: String,
+    auth: String,
+}
+
+/// Send an encrypted Web Push message (RFC 8291 `aes128gcm`) signed with a
+/// VAPID (RFC 8292) `Authorization` header.
+///
+/// Returns [`WebPushError::Gone`] on a `404`/`410` response so the caller
+/// can prune the dead subscription from DynamoDB.
+pub async fn send_web_push(
+    subscription_json: &str,
+    payload: &serde_json::Value,
+    keys: &WebPushKeys,
+) -> Result<(), WebPushError> {
+    let sub: PushSubscription = serde_json::from_str(subscription_json)
+        .map_err(|e| WebPushError::InvalidSubscription(e.to_string()))?;
+
+    let plaintext = serde_json::to_vec(payload)
+        .map_err(|e| WebPushError::InvalidSubscription(e.to_string()))?;
+    let body = encrypt_aes128gcm(&sub.keys.p256dh, &sub.keys.auth, &plaintext)?;
+
+    let audience = push_origin(&sub.endpoint)?;
+    let authorization = vapid_header(&audience, keys)?;
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(PUSH_REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(|e| WebPushError::Request(e.to_string()))?;
+
+    let resp = client
+        .post(&sub.endpoint)
+        .header("Authorization", authorization)
+        .header("Content-Encoding", "aes128gcm")
+        .header("Content-Type", "application/octet-stream")
+        .header("TTL", "86400")
+        .header("Urgency", "normal")
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| WebPushError::Request(e.to_string()))?;
+
+    match resp.status().as_u16() {
+        200..=299 => Ok(()),
+        404 | 410 => Err(WebPushError::Gone(resp.status().as_u16())),
+        code => Err(WebPushError::Request(format!("push service returned {}", code))),
+    }
 }
 
-// Note: web-push crate integration would go here for real push sending.
-// For now we provide the struct and a stub function that tool handlers call.
-// The actual web-push sending is complex and depends on the web-push crate's API,
-// so we'll keep it as a best-effort operation that logs errors.
-
-/// Send a web push notification. Returns Ok(()) on success or logs/returns error.
-pub fn send_web_push(
-    _subscription_json: &str,
-    _payload: &serde_json::Value,
-    _keys: &WebPushKeys,
-) -> Result<(), String> {
-    // In production, this would use the web-push crate.
-    // For now, this is a stub that succeeds (since web-push requires
-    // actual VAPID keys and browser subscriptions to test).
-    tracing::debug!("web push send (stub)");
-    Ok(())
+/// The `scheme://host` of a push endpoint, used as the VAPID JWT's `aud`.
+fn push_origin(endpoint: &str) -> Result<String, WebPushError> {
+    let url = reqwest::Url::parse(endpoint)
+        .map_err(|e| WebPushError::InvalidSubscription(e.to_string()))?;
+    Ok(format!("{}://{}", url.scheme(), url.host_str().unwrap_or_default()))
+}
+
+/// Builds the `vapid t=<jwt>, k=<public key>` Authorization header value.
+fn vapid_header(audience: &str, keys: &WebPushKeys) -> Result<String, WebPushError> {
+    let private_bytes = URL_SAFE_NO_PAD
+        .decode(&keys.vapid_private_key)
+        .map_err(|e| WebPushError::InvalidKeys(e.to_string()))?;
+    let signing_key = SigningKey::from_bytes((&private_bytes[..]).into())
+        .map_err(|e| WebPushError::InvalidKeys(e.to_string()))?;
+
+    let exp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+        + 12 * 3600;
+
+    let header = URL_SAFE_NO_PAD.encode(br#"{"typ":"JWT","alg":"ES256"}"#);
+    let claims = serde_json::json!({
+        "aud": audience,
+        "exp": exp,
+        "sub": keys.vapid_subject,
+    });
+    let signing_input = format!("{}.{}", header, URL_SAFE_NO_PAD.encode(claims.to_string()));
+
+    let signature: Signature = signing_key.sign(signing_input.as_bytes());
+    let jwt = format!("{}.{}", signing_input, URL_SAFE_NO_PAD.encode(signature.to_bytes()));
+
+    Ok(format!("vapid t={}, k={}", jwt, keys.vapid_public_key))
+}
+
+/// RFC 8291 message encryption: ECDH with the subscriber's `p256dh` key,
+/// HKDF-derive the content-encryption key and nonce (salted with a fresh
+/// random salt and the subscription's `auth` secret), then AES-128-GCM the
+/// padded plaintext as a single `aes128gcm` (RFC 8188) record.
+fn encrypt_aes128gcm(p256dh_b64: &str, auth_b64: &str, plaintext: &[u8]) -> Result<Vec<u8>, WebPushError> {
+    let subscriber_public_bytes = URL_SAFE_NO_PAD
+        .decode(p256dh_b64)
+        .map_err(|e| WebPushError::InvalidSubscription(e.to_string()))?;
+    let auth_secret = URL_SAFE_NO_PAD
+        .decode(auth_b64)
+        .map_err(|e| WebPushError::InvalidSubscription(e.to_string()))?;
+    let subscriber_public = PublicKey::from_sec1_bytes(&subscriber_public_bytes)
+        .map_err(|e| WebPushError::InvalidSubscription(e.to_string()))?;
+
+    // Ephemeral keypair, used for this message only.
+    let ephemeral_secret = SecretKey::random(&mut rand::rng());
+    let ephemeral_public_bytes = ephemeral_secret
+        .public_key()
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec();
+    let shared_secret = diffie_hellman(
+        ephemeral_secret.to_nonzero_scalar(),
+        subscriber_public.as_affine(),
+    );
+
+    let mut salt = [0u8; 16];
+    rand::rng().fill_bytes(&mut salt);
+
+    // RFC 8291 section 3.3/3.4: derive a PRK from the ECDH output keyed by
+    // the subscription's auth secret and combined with both public keys,
+    // then derive the content-encryption key and nonce from it.
+    let key_info = [
+        b"WebPush: info\0".as_slice(),
+        &subscriber_public_bytes,
+        &ephemeral_public_bytes,
+    ]
+    .concat();
+
+    let (prk, _) = Hkdf::<Sha256>::extract(Some(&auth_secret), shared_secret.raw_secret_bytes());
+    let ikm_hk = Hkdf::<Sha256>::from_prk(&prk).map_err(|e| WebPushError::Encryption(e.to_string()))?;
+    let mut ikm = [0u8; 32];
+    ikm_hk
+        .expand(&key_info, &mut ikm)
+        .map_err(|e| WebPushError::Encryption(e.to_string()))?;
+
+    let cek_hk = Hkdf::<Sha256>::new(Some(&salt), &ikm);
+    let mut cek = [0u8; 16];
+    cek_hk
+        .expand(b"Content-Encoding: aes128gcm\0", &mut cek)
+        .map_err(|e| WebPushError::Encryption(e.to_string()))?;
+    let mut nonce_bytes = [0u8; 12];
+    cek_hk
+        .expand(b"Content-Encoding: nonce\0", &mut nonce_bytes)
+        .map_err(|e| WebPushError::Encryption(e.to_string()))?;
+
+    // Single-record aes128gcm body: a trailing 0x02 delimiter marks this as
+    // the last (only) record, then zero-pad out to RECORD_SIZE so the
+    // ciphertext length is constant regardless of payload size.
+    let target_len = RECORD_SIZE as usize - 16; // minus the AEAD tag
+    if plaintext.len() + 1 > target_len {
+        return Err(WebPushError::PayloadTooLarge(plaintext.len()));
+    }
+    let mut padded = plaintext.to_vec();
+    padded.push(0x02);
+    padded.resize(target_len, 0);
+
+    let cipher = Aes128Gcm::new_from_slice(&cek).map_err(|e| WebPushError::Encryption(e.to_string()))?;
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce_bytes), Payload { msg: &padded, aad: b"" })
+        .map_err(|e| WebPushError::Encryption(e.to_string()))?;
+
+    // aes128gcm header (RFC 8188 section 2.1): salt(16) | record size(4,
+    // big-endian) | keyid length(1) | keyid (the ephemeral public key).
+    let mut body = Vec::with_capacity(21 + ephemeral_public_bytes.len() + ciphertext.len());
+    body.extend_from_slice(&salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(ephemeral_public_bytes.len() as u8);
+    body.extend_from_slice(&ephemeral_public_bytes);
+    body.extend_from_slice(&ciphertext);
+
+    Ok(body)
 }
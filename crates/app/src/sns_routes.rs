@@ -0,0 +1,33 @@
+//! `POST /sns` — lets this service be an SNS HTTPS subscriber, not just a
+//! publisher via [`crate::notify::sns::SnsApi`]. Mounted alongside the MCP
+//! router in `main` for the axum (non-Lambda) build; the Lambda handler
+//! matches this path directly since it doesn't delegate to an axum
+//! `Router`.
+
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::{Json, Router};
+
+use crate::notify::sns_inbound::{verify_and_handle, SnsEnvelope};
+
+/// Build the `/sns` router, to be merged into the main axum `Router`.
+pub fn router() -> Router {
+    Router::new().route("/sns", post(handle_sns))
+}
+
+async fn handle_sns(Json(envelope): Json<SnsEnvelope>) -> StatusCode {
+    match verify_and_handle(envelope).await {
+        Ok(Some(message)) => {
+            // The inner `Message` isn't itself a JSON-RPC request — callers
+            // who want it dispatched as one (e.g. a tool invocation) are
+            // expected to shape their SNS message body that way upstream.
+            tracing::info!(%message, "received SNS notification");
+            StatusCode::OK
+        }
+        Ok(None) => StatusCode::OK,
+        Err(e) => {
+            tracing::warn!(error = %e, "rejected inbound SNS message");
+            StatusCode::BAD_REQUEST
+        }
+    }
+}
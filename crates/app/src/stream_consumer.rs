@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use lambda_runtime::{Error, LambdaEvent};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::tools::subscription_pattern;
+use crate::tools::Deps;
+
+/// A DynamoDB Streams event batch, as Lambda delivers it when this
+/// function is subscribed to the table's stream ARN. Each record carries
+/// its image in DynamoDB's own wire format (`{"S": "..."}`, `{"M": {...}}`,
+/// etc.), not plain JSON — see [`attribute_value_to_json`].
+#[derive(Debug, Deserialize)]
+struct StreamEvent {
+    #[serde(rename = "Records")]
+    records: Vec<StreamRecord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamRecord {
+    #[serde(rename = "eventName")]
+    event_name: String,
+    dynamodb: StreamRecordPayload,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamRecordPayload {
+    #[serde(rename = "NewImage")]
+    new_image: Option<HashMap<String, Value>>,
+}
+
+/// Run the DynamoDB-Streams-triggered Lambda handler: evaluate every
+/// `INSERT`/`MODIFY` record's `NEW_IMAGE` against the pattern subscriptions
+/// stored for its topic, fanning matches out through
+/// [`subscription_pattern::dispatch_stream_record`]. Separate from
+/// [`crate::lambda::run`] because it's triggered by the table's stream ARN
+/// rather than by HTTP — a distinct Lambda function in the same deploy.
+pub async fn run(deps: Arc<Deps>) {
+    let func = lambda_runtime::service_fn(move |event: LambdaEvent<StreamEvent>| {
+        let deps = deps.clone();
+        async move { handle_event(&deps, event).await }
+    });
+
+    if let Err(e) = lambda_runtime::run(func).await {
+        tracing::error!("stream consumer exited: {}", e);
+    }
+}
+
+async fn handle_event(deps: &Deps, event: LambdaEvent<StreamEvent>) -> Result<(), Error> {
+    for record in event.payload.records {
+        if record.event_name != "INSERT" && record.event_name != "MODIFY" {
+            continue;
+        }
+        let Some(raw_image) = record.dynamodb.new_image else { continue };
+
+        let item: HashMap<String, Value> = raw_image
+            .into_iter()
+            .map(|(k, v)| (k, attribute_value_to_json(&v)))
+            .collect();
+
+        let topic = item
+            .get("PK")
+            .and_then(|v| v.as_str())
+            .and_then(|pk| pk.split(':').next())
+            .unwrap_or_default();
+        if topic.is_empty() {
+            continue;
+        }
+
+        let dispatched = subscription_pattern::dispatch_stream_record(deps, topic, &item).await;
+        if dispatched > 0 {
+            tracing::info!(topic, dispatched, "pattern subscriptions notified");
+        }
+    }
+    Ok(())
+}
+
+/// Convert one DynamoDB Streams attribute value (still in its wire
+/// format, e.g. `{"S": "foo"}`) into the plain JSON value every other
+/// `HashMap<String, Value>` item in this crate is shaped as, recursing
+/// into `L`/`M` so nested fields round-trip the same way
+/// [`subscription_pattern::match_item`] expects.
+fn attribute_value_to_json(av: &Value) -> Value {
+    let Some(obj) = av.as_object() else { return Value::Null };
+
+    if let Some(s) = obj.get("S").and_then(|v| v.as_str()) {
+        return Value::String(s.to_string());
+    }
+    if let Some(n) = obj.get("N").and_then(|v| v.as_str()) {
+        return n
+            .parse::<i64>()
+            .map(Value::from)
+            .or_else(|_| n.parse::<f64>().map(Value::from))
+            .unwrap_or(Value::Null);
+    }
+    if let Some(b) = obj.get("BOOL").and_then(|v| v.as_bool()) {
+        return Value::Bool(b);
+    }
+    if obj.get("NULL").and_then(|v| v.as_bool()).unwrap_or(false) {
+        return Value::Null;
+    }
+    if let Some(list) = obj.get("L").and_then(|v| v.as_array()) {
+        return Value::Array(list.iter().map(attribute_value_to_json).collect());
+    }
+    if let Some(map) = obj.get("M").and_then(|v| v.as_object()) {
+        return Value::Object(
+            map.iter()
+                .map(|(k, v)| (k.clone(), attribute_value_to_json(v)))
+                .collect(),
+        );
+    }
+    Value::Null
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attribute_value_to_json_string() {
+        let av = serde_json::json!({"S": "hello"});
+        assert_eq!(attribute_value_to_json(&av), Value::String("hello".into()));
+    }
+
+    #[test]
+    fn test_attribute_value_to_json_number() {
+        let av = serde_json::json!({"N": "42"});
+        assert_eq!(attribute_value_to_json(&av), Value::from(42i64));
+
+        let av = serde_json::json!({"N": "4.5"});
+        assert_eq!(attribute_value_to_json(&av), Value::from(4.5f64));
+    }
+
+    #[test]
+    fn test_attribute_value_to_json_bool() {
+        let av = serde_json::json!({"BOOL": true});
+        assert_eq!(attribute_value_to_json(&av), Value::Bool(true));
+    }
+
+    #[test]
+    fn test_attribute_value_to_json_null() {
+        let av = serde_json::json!({"NULL": true});
+        assert_eq!(attribute_value_to_json(&av), Value::Null);
+    }
+
+    #[test]
+    fn test_attribute_value_to_json_list() {
+        let av = serde_json::json!({"L": [{"S": "a"}, {"N": "1"}]});
+        assert_eq!(
+            attribute_value_to_json(&av),
+            Value::Array(vec![Value::String("a".into()), Value::from(1i64)]),
+        );
+    }
+
+    #[test]
+    fn test_attribute_value_to_json_map() {
+        let av = serde_json::json!({"M": {"name": {"S": "Alice"}}});
+        let expected = serde_json::json!({"name": "Alice"});
+        assert_eq!(attribute_value_to_json(&av), expected);
+    }
+}
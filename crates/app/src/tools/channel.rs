@@ -1,15 +1,63 @@
 use std::sync::Arc;
 use std::collections::HashMap;
+use std::time::Duration;
 
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use dashmap::DashMap;
 use mcpserver::{text_result, error_result, FnToolHandler, ToolResult, McpError};
 use serde_json::Value;
+use tokio::sync::watch;
 
 use crate::auth;
-use crate::dynamo::KeyPair;
+use crate::dynamo::{DynamoError, GsiKeyPair, KeyPair, PutRequest};
+use super::channel_stream::ChannelEvent;
 use super::Deps;
 
 const NANOID_ALPHABET: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
 
+/// Server-side cap on `channel-poll`'s `timeout` argument, so a client
+/// can't tie up a connection (and a `ChannelChangeRegistry` receiver)
+/// indefinitely.
+const MAX_POLL_TIMEOUT_SECS: u64 = 60;
+
+/// In-process "something changed" signal per channel id, for
+/// `channel-poll`'s long-poll wait. Carries a monotonic counter rather
+/// than the item itself — `channel-poll` always re-reads current state
+/// from `DynamoApi` after waking, so the watched value only needs to
+/// prove *that* a write happened, not *what* changed.
+#[derive(Default)]
+pub struct ChannelChangeRegistry {
+    channels: DashMap<String, watch::Sender<u64>>,
+}
+
+impl ChannelChangeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn sender(&self, channel_id: &str) -> watch::Sender<u64> {
+        self.channels
+            .entry(channel_id.to_string())
+            .or_insert_with(|| watch::channel(0).0)
+            .clone()
+    }
+
+    /// Signal that `channel_id` changed — called after a successful
+    /// `channel-put`/`channel-delete` write.
+    pub fn signal(&self, channel_id: &str) {
+        let tx = self.sender(channel_id);
+        let next = tx.borrow().wrapping_add(1);
+        let _ = tx.send(next);
+    }
+
+    /// A receiver that resolves the next time [`ChannelChangeRegistry::signal`]
+    /// is called for `channel_id`.
+    pub fn watch(&self, channel_id: &str) -> watch::Receiver<u64> {
+        self.sender(channel_id).subscribe()
+    }
+}
+
 pub fn register(srv: &mut mcpserver::Server, deps: Arc<Deps>) {
     let d = deps.clone();
     srv.handle_tool("channel-put", FnToolHandler::new(move |args: Value| {
@@ -29,11 +77,41 @@ pub fn register(srv: &mut mcpserver::Server, deps: Arc<Deps>) {
         async move { handle_channels_list(&deps, args).await }
     }));
 
-    let d = deps;
+    let d = deps.clone();
     srv.handle_tool("channels-for-category", FnToolHandler::new(move |args: Value| {
         let deps = d.clone();
         async move { handle_channels_for_category(&deps, args).await }
     }));
+
+    let d = deps.clone();
+    srv.handle_tool("channels-batch-put", FnToolHandler::new(move |args: Value| {
+        let deps = d.clone();
+        async move { handle_channels_batch_put(&deps, args).await }
+    }));
+
+    let d = deps.clone();
+    srv.handle_tool("channels-batch-delete", FnToolHandler::new(move |args: Value| {
+        let deps = d.clone();
+        async move { handle_channels_batch_delete(&deps, args).await }
+    }));
+
+    let d = deps.clone();
+    srv.handle_tool("channels-batch-read", FnToolHandler::new(move |args: Value| {
+        let deps = d.clone();
+        async move { handle_channels_batch_read(&deps, args).await }
+    }));
+
+    let d = deps.clone();
+    srv.handle_tool("channels-index", FnToolHandler::new(move |args: Value| {
+        let deps = d.clone();
+        async move { handle_channels_index(&deps, args).await }
+    }));
+
+    let d = deps;
+    srv.handle_tool("channel-poll", FnToolHandler::new(move |args: Value| {
+        let deps = d.clone();
+        async move { handle_channel_poll(&deps, args).await }
+    }));
 }
 
 async fn handle_channel_put(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
@@ -79,7 +157,42 @@ async fn handle_channel_put(deps: &Deps, args: Value) -> Result<ToolResult, McpE
         attrs.insert("geo_lon".into(), serde_json::json!(lon));
     }
 
-    if let Err(e) = deps.db.put_item(
+    // Optimistic concurrency (K2V-style causal contexts): compare the
+    // caller's `causalContext` against the stored version vector before
+    // overwriting an existing channel, then enforce it for real via a
+    // conditional write against that exact stored version — a pre-read
+    // check alone can't stop two overlapping `channel-put`s for the same
+    // channel from both reading the same version and both writing; only
+    // one of them wins the `put_item_if_version` race, and the loser is
+    // told to retry rather than silently clobbering the winner.
+    let incoming_context = args
+        .get("causalContext")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(decode_context);
+
+    let mut version: HashMap<String, u64> = HashMap::new();
+    let mut expected_version: Option<HashMap<String, u64>> = None;
+    if !channel_arg.is_empty() {
+        if let Ok(Some(existing)) = deps.db.get_item(&format!("channel:{}", user_id), channel_arg).await {
+            let stored_version = version_of(&existing);
+            if let Some(incoming) = &incoming_context {
+                match incoming {
+                    Some(incoming) if dominates(incoming, &stored_version) => {}
+                    Some(_) => return Ok(error_result("conflict: causal context is stale")),
+                    None => return Ok(error_result("invalid causal context")),
+                }
+            }
+            version = stored_version.clone();
+            expected_version = Some(stored_version);
+        }
+    }
+    *version.entry(user_id.clone()).or_insert(0) += 1;
+    attrs.insert("version".into(), serde_json::to_value(&version).unwrap_or(Value::Null));
+
+    let is_new_channel = channel_arg.is_empty();
+
+    match deps.db.put_item_if_version(
         &format!("channel:{}", user_id),
         &channel_id,
         "channel",
@@ -87,15 +200,77 @@ async fn handle_channel_put(deps: &Deps, args: Value) -> Result<ToolResult, McpE
         "channel",
         &category.to_uppercase(),
         attrs,
+        expected_version.as_ref(),
     ).await {
-        tracing::error!("channel-put: {}", e);
-        return Ok(error_result("failed to create channel"));
+        Ok(()) => {}
+        Err(DynamoError::ConditionalCheckFailed) => {
+            return Ok(error_result("conflict: channel was modified concurrently, retry with a fresh causal context"));
+        }
+        Err(e) => {
+            tracing::error!("channel-put: {}", e);
+            return Ok(error_result("failed to create channel"));
+        }
+    }
+
+    // Maintain the `channels-index` counter — only on genuine creation, so
+    // an edit to an existing channel doesn't double-count it.
+    if is_new_channel {
+        if let Err(e) = deps.db.increment_counter("channel-count", &category.to_uppercase(), "count", 1).await {
+            tracing::error!("channel-put increment count category={}: {}", category, e);
+        }
     }
 
-    let result = serde_json::json!({"channelId": channel_id});
+    deps.channel_versions.signal(&channel_id);
+
+    let result = serde_json::json!({"channelId": channel_id, "causalContext": encode_context(&version)});
     Ok(text_result(&result.to_string()))
 }
 
+/// A channel item's `version` attr (`{writerId -> counter}`), or empty if
+/// the item predates causal contexts / has never been written.
+fn version_of(item: &HashMap<String, Value>) -> HashMap<String, u64> {
+    item.get("version")
+        .cloned()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default()
+}
+
+/// Opaque base64 token for a channel's version vector — returned
+/// alongside reads (`channels-list`/`channels-for-category`) and accepted
+/// back by `channel-put`'s optional `causalContext` argument.
+fn encode_context(version: &HashMap<String, u64>) -> String {
+    BASE64.encode(serde_json::to_vec(version).unwrap_or_default())
+}
+
+/// `None` means the token failed to decode — callers should treat that as
+/// an invalid context, not as "no context given" (that's a bare `None` one
+/// level up, before this is even called).
+fn decode_context(token: &str) -> Option<HashMap<String, u64>> {
+    let bytes = BASE64.decode(token).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// `true` if `incoming` causally dominates (has seen everything in)
+/// `stored` — every writer's counter in `stored` is `<=` the
+/// corresponding counter in `incoming`.
+fn dominates(incoming: &HashMap<String, u64>, stored: &HashMap<String, u64>) -> bool {
+    stored.iter().all(|(writer, &count)| incoming.get(writer).copied().unwrap_or(0) >= count)
+}
+
+/// Attach each item's `causalContext` token alongside its fields, for
+/// `channels-list`/`channels-for-category` responses.
+fn annotate_causal_context(items: Vec<HashMap<String, Value>>) -> Vec<Value> {
+    items
+        .into_iter()
+        .map(|item| {
+            let version = version_of(&item);
+            let mut obj: serde_json::Map<String, Value> = item.into_iter().collect();
+            obj.insert("causalContext".into(), Value::String(encode_context(&version)));
+            Value::Object(obj)
+        })
+        .collect()
+}
+
 async fn handle_channel_delete(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
     let user_id = match authenticate(deps, &args) {
         Ok(id) => id,
@@ -107,12 +282,28 @@ async fn handle_channel_delete(deps: &Deps, args: Value) -> Result<ToolResult, M
         return Ok(error_result("channel required"));
     }
 
+    // Looked up before deleting so the `channels-index` counter is only
+    // decremented when this call actually removes an existing item.
+    let existing = match deps.db.get_item(&format!("channel:{}", user_id), channel_id).await {
+        Ok(existing) => existing,
+        Err(e) => {
+            tracing::error!("channel-delete lookup: {}", e);
+            return Ok(error_result("failed to delete channel"));
+        }
+    };
+
     // Delete the channel itself.
     if let Err(e) = deps.db.delete_item(&format!("channel:{}", user_id), channel_id).await {
         tracing::error!("channel-delete: {}", e);
         return Ok(error_result("failed to delete channel"));
     }
 
+    if let Some(category) = existing.as_ref().and_then(|item| item.get("category")).and_then(|v| v.as_str()) {
+        if let Err(e) = deps.db.increment_counter("channel-count", &category.to_uppercase(), "count", -1).await {
+            tracing::error!("channel-delete decrement count category={}: {}", category, e);
+        }
+    }
+
     // Cascade: find and delete all subscriptions to this channel via GSI1.
     match deps.db.query_gsi_with_sk("GSI1", "subscription", channel_id).await {
         Ok(subs) if !subs.is_empty() => {
@@ -133,25 +324,73 @@ async fn handle_channel_delete(deps: &Deps, args: Value) -> Result<ToolResult, M
         _ => {}
     }
 
+    deps.channel_streams.publish(
+        channel_id,
+        ChannelEvent::ChannelDeleted {
+            channel: channel_id.to_string(),
+        },
+    );
+    deps.channel_versions.signal(channel_id);
+
     Ok(text_result("channel deleted"))
 }
 
+/// `limit`/`start` args shared by `channels-list`/`channels-for-category`.
+/// `limit` absent means "no pagination" — callers should keep returning
+/// the old bare-array shape so existing integrations don't break. `limit`
+/// present but not a positive integer is rejected outright rather than
+/// silently falling back to the unpaginated shape, so a typo doesn't
+/// quietly return an entire partition instead of one page of it.
+fn pagination_args(args: &Value) -> Result<(Option<i32>, Option<String>), String> {
+    let limit = match args.get("limit") {
+        None | Some(Value::Null) => None,
+        Some(v) => {
+            let n = v.as_u64().filter(|&n| n > 0).ok_or("limit must be a positive integer")?;
+            Some(n.min(i32::MAX as u64) as i32)
+        }
+    };
+    let start = args.get("start").and_then(|v| v.as_str()).filter(|s| !s.is_empty()).map(|s| s.to_string());
+    Ok((limit, start))
+}
+
 async fn handle_channels_list(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
     let user_id = match authenticate(deps, &args) {
         Ok(id) => id,
         Err(msg) => return Ok(error_result(&msg)),
     };
 
-    let items = match deps.db.query(&format!("channel:{}", user_id)).await {
-        Ok(items) => items,
+    let (limit, start) = match pagination_args(&args) {
+        Ok(parsed) => parsed,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+    let pk = format!("channel:{}", user_id);
+
+    // No `limit` => old unpaginated shape, for callers written before
+    // pagination existed.
+    let Some(limit) = limit else {
+        let items = match deps.db.query(&pk).await {
+            Ok(items) => items,
+            Err(e) => {
+                tracing::error!("channels-list: {}", e);
+                return Ok(error_result("failed to list channels"));
+            }
+        };
+        let items = annotate_causal_context(items);
+        return Ok(text_result(&serde_json::to_string(&items).unwrap_or_else(|_| "[]".into())));
+    };
+
+    let exclusive_start_key = start.map(|sk| KeyPair { pk: pk.clone(), sk });
+    let page = match deps.db.query_page(&pk, exclusive_start_key, Some(limit)).await {
+        Ok(page) => page,
         Err(e) => {
             tracing::error!("channels-list: {}", e);
             return Ok(error_result("failed to list channels"));
         }
     };
 
-    let buf = serde_json::to_string(&items).unwrap_or_else(|_| "[]".into());
-    Ok(text_result(&buf))
+    let items = annotate_causal_context(page.items);
+    let next_start = page.last_key.map(|k| Value::String(k.sk)).unwrap_or(Value::Null);
+    Ok(text_result(&serde_json::json!({"items": items, "nextStart": next_start}).to_string()))
 }
 
 async fn handle_channels_for_category(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
@@ -163,17 +402,437 @@ async fn handle_channels_for_category(deps: &Deps, args: Value) -> Result<ToolRe
     if category.is_empty() {
         return Ok(error_result("category required"));
     }
+    let category = category.to_uppercase();
 
-    let items = match deps.db.query_gsi_with_sk("GSI2", "channel", &category.to_uppercase()).await {
-        Ok(items) => items,
+    let (limit, start) = match pagination_args(&args) {
+        Ok(parsed) => parsed,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+
+    // No `limit` => old unpaginated shape, for callers written before
+    // pagination existed.
+    let Some(limit) = limit else {
+        let items = match deps.db.query_gsi_with_sk("GSI2", "channel", &category).await {
+            Ok(items) => items,
+            Err(e) => {
+                tracing::error!("channels-for-category: {}", e);
+                return Ok(error_result("failed to query channels"));
+            }
+        };
+        let items = annotate_causal_context(items);
+        return Ok(text_result(&serde_json::to_string(&items).unwrap_or_else(|_| "[]".into())));
+    };
+
+    // `start`, when given, is the opaque token this handler returned as
+    // `nextStart` last time — a GSI's `ExclusiveStartKey` needs the base
+    // table's own primary key alongside the index's, so a bare SK string
+    // (like `channels-list` uses) isn't enough here.
+    let exclusive_start_key = match start.map(|token| decode_gsi_key(&token, "channel", &category)) {
+        Some(Some(key)) => Some(key),
+        Some(None) => return Ok(error_result("invalid start token")),
+        None => None,
+    };
+
+    let page = match deps.db.query_gsi_with_sk_page("GSI2", "channel", &category, exclusive_start_key, Some(limit)).await {
+        Ok(page) => page,
         Err(e) => {
             tracing::error!("channels-for-category: {}", e);
             return Ok(error_result("failed to query channels"));
         }
     };
 
-    let buf = serde_json::to_string(&items).unwrap_or_else(|_| "[]".into());
-    Ok(text_result(&buf))
+    let items = annotate_causal_context(page.items);
+    let next_start = page.last_key.map(|k| Value::String(encode_gsi_key(&k))).unwrap_or(Value::Null);
+    Ok(text_result(&serde_json::json!({"items": items, "nextStart": next_start}).to_string()))
+}
+
+/// Opaque `nextStart` token for [`handle_channels_for_category`]'s
+/// pagination — base64 of the record's own `(pk, sk)`, the same
+/// encoding style as a causal context token.
+fn encode_gsi_key(key: &GsiKeyPair) -> String {
+    BASE64.encode(serde_json::json!({"pk": key.pk, "sk": key.sk}).to_string())
+}
+
+/// Decodes an [`encode_gsi_key`] token back into a [`GsiKeyPair`],
+/// filling in `gsi_pk`/`gsi_sk` from the query's own arguments since
+/// they're fixed for the whole page and not worth encoding. `None` means
+/// the token failed to decode.
+fn decode_gsi_key(token: &str, gsi_pk: &str, gsi_sk: &str) -> Option<GsiKeyPair> {
+    let bytes = BASE64.decode(token).ok()?;
+    let value: Value = serde_json::from_slice(&bytes).ok()?;
+    let pk = value.get("pk")?.as_str()?.to_string();
+    let sk = value.get("sk")?.as_str()?.to_string();
+    Some(GsiKeyPair { pk, sk, gsi_pk: gsi_pk.to_string(), gsi_sk: gsi_sk.to_string() })
+}
+
+/// Writes an array of channel objects (each with the same fields
+/// `channel-put` accepts) in one `BatchWriteItem` pass via
+/// `deps.db.batch_put_items`. Returns a per-item success/failure result
+/// so one malformed entry in a bulk import doesn't fail the whole batch.
+async fn handle_channels_batch_put(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
+    let user_id = match authenticate(deps, &args) {
+        Ok(id) => id,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+
+    let channels = match args.get("channels").and_then(|v| v.as_array()) {
+        Some(arr) => arr,
+        None => return Ok(error_result("channels required")),
+    };
+
+    let mut puts = Vec::new();
+    let mut new_channel_categories = Vec::new();
+    let mut results = Vec::with_capacity(channels.len());
+
+    for channel in channels {
+        let name = channel.get("name").and_then(|v| v.as_str()).unwrap_or("");
+        let category = channel.get("category").and_then(|v| v.as_str()).unwrap_or("");
+        let poster = channel.get("poster").and_then(|v| v.as_str()).unwrap_or("");
+        let channel_arg = channel.get("channel").and_then(|v| v.as_str()).unwrap_or("");
+
+        if name.is_empty() || category.is_empty() {
+            results.push(serde_json::json!({"success": false, "error": "name and category required"}));
+            continue;
+        }
+
+        let channel_id = if !channel_arg.is_empty() {
+            channel_arg.to_string()
+        } else if category.eq_ignore_ascii_case("VEHICLES") {
+            name.to_uppercase().replace(' ', "")
+        } else {
+            nanoid(7)
+        };
+
+        let mut attrs: HashMap<String, Value> = HashMap::new();
+        attrs.insert("name".into(), Value::String(name.into()));
+        attrs.insert("category".into(), Value::String(category.into()));
+        attrs.insert("poster".into(), Value::String(poster.into()));
+        attrs.insert("owner".into(), Value::String(user_id.clone()));
+
+        if let Some(desc) = channel.get("description").and_then(|v| v.as_str()) {
+            if !desc.is_empty() {
+                attrs.insert("description".into(), Value::String(desc.into()));
+            }
+        }
+        if let Some(addr) = channel.get("address").and_then(|v| v.as_str()) {
+            if !addr.is_empty() {
+                attrs.insert("address".into(), Value::String(addr.into()));
+            }
+        }
+        if let Some(lat) = channel.get("geo_lat").and_then(|v| v.as_f64()) {
+            attrs.insert("geo_lat".into(), serde_json::json!(lat));
+        }
+        if let Some(lon) = channel.get("geo_lon").and_then(|v| v.as_f64()) {
+            attrs.insert("geo_lon".into(), serde_json::json!(lon));
+        }
+
+        // Same causal-context merge `channel-put` does — a batch item
+        // editing an existing channel must build on its stored version
+        // vector, not stomp it down to a fresh single-entry map, or every
+        // other writer's contributions (and any client's held
+        // `causalContext` token) are silently corrupted.
+        let incoming_context = channel
+            .get("causalContext")
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(decode_context);
+
+        let mut version: HashMap<String, u64> = HashMap::new();
+        if !channel_arg.is_empty() {
+            match deps.db.get_item(&format!("channel:{}", user_id), channel_arg).await {
+                Ok(Some(existing)) => {
+                    let stored_version = version_of(&existing);
+                    if let Some(incoming) = &incoming_context {
+                        match incoming {
+                            Some(incoming) if dominates(incoming, &stored_version) => {}
+                            Some(_) => {
+                                results.push(serde_json::json!({
+                                    "success": false,
+                                    "channelId": channel_id,
+                                    "error": "conflict: causal context is stale",
+                                }));
+                                continue;
+                            }
+                            None => {
+                                results.push(serde_json::json!({
+                                    "success": false,
+                                    "channelId": channel_id,
+                                    "error": "invalid causal context",
+                                }));
+                                continue;
+                            }
+                        }
+                    }
+                    version = stored_version;
+                }
+                Ok(None) => {}
+                Err(e) => tracing::error!("channels-batch-put lookup channel={}: {}", channel_id, e),
+            }
+        }
+        *version.entry(user_id.clone()).or_insert(0) += 1;
+        attrs.insert("version".into(), serde_json::to_value(&version).unwrap_or(Value::Null));
+
+        if channel_arg.is_empty() {
+            new_channel_categories.push(category.to_uppercase());
+        }
+
+        puts.push(PutRequest {
+            pk: format!("channel:{}", user_id),
+            sk: channel_id.clone(),
+            gsi1pk: "channel".into(),
+            gsi1sk: channel_id.clone(),
+            gsi2pk: "channel".into(),
+            gsi2sk: category.to_uppercase(),
+            attrs,
+        });
+        results.push(serde_json::json!({"success": true, "channelId": channel_id}));
+    }
+
+    if !puts.is_empty() {
+        if let Err(e) = deps.db.batch_put_items(&puts).await {
+            tracing::error!("channels-batch-put: {}", e);
+            return Ok(error_result("failed to write channels"));
+        }
+        for put in &puts {
+            deps.channel_versions.signal(&put.sk);
+        }
+    }
+
+    // Maintain the `channels-index` counters the same way `channel-put`
+    // does — only for genuine creations, so re-putting an existing
+    // channel through this batch path doesn't double-count it.
+    for category in tally(&new_channel_categories) {
+        if let Err(e) = deps.db.increment_counter("channel-count", &category.0, "count", category.1).await {
+            tracing::error!("channels-batch-put increment count category={}: {}", category.0, e);
+        }
+    }
+
+    Ok(text_result(&serde_json::json!({"results": results}).to_string()))
+}
+
+/// Collapses a list of repeated keys into `(key, count)` pairs, so a
+/// batch touching several channels in the same category issues one
+/// `increment_counter` call per category instead of one per channel.
+fn tally(keys: &[String]) -> Vec<(String, i64)> {
+    let mut counts: HashMap<String, i64> = HashMap::new();
+    for key in keys {
+        *counts.entry(key.clone()).or_insert(0) += 1;
+    }
+    counts.into_iter().collect()
+}
+
+/// Deletes an array of channel IDs plus their subscription cascades in
+/// one `batch_delete_items` pass. Returns a per-item success/failure
+/// result; an invalid channel ID in the array doesn't block the rest.
+async fn handle_channels_batch_delete(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
+    let user_id = match authenticate(deps, &args) {
+        Ok(id) => id,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+
+    let channel_ids = match args.get("channels").and_then(|v| v.as_array()) {
+        Some(arr) => arr,
+        None => return Ok(error_result("channels required")),
+    };
+
+    let mut keys = Vec::new();
+    let mut deleted_channel_ids = Vec::new();
+    let mut deleted_categories = Vec::new();
+    let mut results = Vec::with_capacity(channel_ids.len());
+
+    for channel in channel_ids {
+        let channel_id = match channel.as_str().filter(|id| !id.is_empty()) {
+            Some(id) => id,
+            None => {
+                results.push(serde_json::json!({"success": false, "error": "invalid channel id"}));
+                continue;
+            }
+        };
+
+        // Looked up before deleting, the same as `channel-delete`, so the
+        // `channels-index` counter is only decremented for channels that
+        // actually existed.
+        match deps.db.get_item(&format!("channel:{}", user_id), channel_id).await {
+            Ok(Some(existing)) => {
+                if let Some(category) = existing.get("category").and_then(|v| v.as_str()) {
+                    deleted_categories.push(category.to_uppercase());
+                }
+            }
+            Ok(None) => {}
+            Err(e) => tracing::error!("channels-batch-delete lookup channel={}: {}", channel_id, e),
+        }
+
+        keys.push(KeyPair { pk: format!("channel:{}", user_id), sk: channel_id.to_string() });
+
+        match deps.db.query_gsi_with_sk("GSI1", "subscription", channel_id).await {
+            Ok(subs) => keys.extend(subs.iter().filter_map(|s| {
+                let pk = s.get("PK")?.as_str()?;
+                let sk = s.get("SK")?.as_str()?;
+                Some(KeyPair { pk: pk.into(), sk: sk.into() })
+            })),
+            Err(e) => tracing::error!("channels-batch-delete cascade query channel={}: {}", channel_id, e),
+        }
+
+        deleted_channel_ids.push(channel_id.to_string());
+        results.push(serde_json::json!({"success": true, "channelId": channel_id}));
+    }
+
+    if !keys.is_empty() {
+        if let Err(e) = deps.db.batch_delete_items(&keys).await {
+            tracing::error!("channels-batch-delete: {}", e);
+            return Ok(error_result("failed to delete channels"));
+        }
+    }
+
+    // Only notify subscribers/pollers and maintain the `channels-index`
+    // counters once the delete has actually landed — publishing first
+    // would tell them a channel is gone when a failed `batch_delete_items`
+    // could still leave it in the table.
+    for channel_id in &deleted_channel_ids {
+        deps.channel_streams.publish(
+            channel_id,
+            ChannelEvent::ChannelDeleted { channel: channel_id.clone() },
+        );
+        deps.channel_versions.signal(channel_id);
+    }
+    for (category, count) in tally(&deleted_categories) {
+        if let Err(e) = deps.db.increment_counter("channel-count", &category, "count", -count).await {
+            tracing::error!("channels-batch-delete decrement count category={}: {}", category, e);
+        }
+    }
+
+    Ok(text_result(&serde_json::json!({"results": results}).to_string()))
+}
+
+/// Reads an array of channel IDs via `batch_get_items`. IDs with no
+/// matching channel are simply absent from `items`, same as a single
+/// `get_item` miss.
+async fn handle_channels_batch_read(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
+    let user_id = match authenticate(deps, &args) {
+        Ok(id) => id,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+
+    let channel_ids = match args.get("channels").and_then(|v| v.as_array()) {
+        Some(arr) => arr,
+        None => return Ok(error_result("channels required")),
+    };
+
+    let keys: Vec<KeyPair> = channel_ids
+        .iter()
+        .filter_map(|v| v.as_str())
+        .filter(|id| !id.is_empty())
+        .map(|id| KeyPair { pk: format!("channel:{}", user_id), sk: id.to_string() })
+        .collect();
+
+    let items = match deps.db.batch_get_items(&keys).await {
+        Ok(items) => items,
+        Err(e) => {
+            tracing::error!("channels-batch-read: {}", e);
+            return Ok(error_result("failed to read channels"));
+        }
+    };
+
+    let items = annotate_causal_context(items);
+    Ok(text_result(&serde_json::json!({"items": items}).to_string()))
+}
+
+/// Returns the maintained per-category channel counts (`handle_channel_put`/
+/// `handle_channel_delete` keep them in sync) without materializing any
+/// channel items — `{category -> count}`.
+async fn handle_channels_index(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
+    if let Err(msg) = authenticate(deps, &args) {
+        return Ok(error_result(&msg));
+    }
+
+    let items = match deps.db.query("channel-count").await {
+        Ok(items) => items,
+        Err(e) => {
+            tracing::error!("channels-index: {}", e);
+            return Ok(error_result("failed to read channel index"));
+        }
+    };
+
+    let mut counts = serde_json::Map::new();
+    for item in items {
+        let category = match item.get("SK").and_then(|v| v.as_str()) {
+            Some(sk) => sk,
+            None => continue,
+        };
+        let count = item.get("count").and_then(|v| v.as_i64()).unwrap_or(0);
+        counts.insert(category.to_string(), serde_json::json!(count));
+    }
+
+    Ok(text_result(&Value::Object(counts).to_string()))
+}
+
+/// Long-polls a single channel for changes: returns immediately if its
+/// current `causalContext` differs from the one supplied (or the channel
+/// doesn't match what the caller last saw at all), otherwise waits on
+/// `deps.channel_versions` — signalled by `channel-put`/`channel-delete` —
+/// up to `timeout` seconds (capped at [`MAX_POLL_TIMEOUT_SECS`]) for the
+/// next write before reporting no change. Lets a client watch a channel
+/// without a WebSocket, at the cost of holding the request open.
+async fn handle_channel_poll(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
+    let user_id = match authenticate(deps, &args) {
+        Ok(id) => id,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+
+    let channel_id = args.get("channel").and_then(|v| v.as_str()).unwrap_or("");
+    if channel_id.is_empty() {
+        return Ok(error_result("channel required"));
+    }
+
+    let supplied_context = args.get("causalContext").and_then(|v| v.as_str()).filter(|s| !s.is_empty());
+    let timeout_secs = args
+        .get("timeout")
+        .and_then(|v| v.as_u64())
+        .unwrap_or(MAX_POLL_TIMEOUT_SECS)
+        .min(MAX_POLL_TIMEOUT_SECS);
+
+    let current_context = match deps.db.get_item(&format!("channel:{}", user_id), channel_id).await {
+        Ok(item) => item.map(|item| encode_context(&version_of(&item))),
+        Err(e) => {
+            tracing::error!("channel-poll lookup: {}", e);
+            return Ok(error_result("failed to read channel"));
+        }
+    };
+
+    if supplied_context != current_context.as_deref() {
+        return channel_poll_result(deps, &user_id, channel_id).await;
+    }
+
+    let mut rx = deps.channel_versions.watch(channel_id);
+    let waited = tokio::time::timeout(Duration::from_secs(timeout_secs), rx.changed()).await;
+    match waited {
+        Ok(Ok(())) => channel_poll_result(deps, &user_id, channel_id).await,
+        _ => Ok(text_result(&serde_json::json!({"changed": false}).to_string())),
+    }
+}
+
+/// Re-reads `channel_id`'s current item and shapes it into `channel-poll`'s
+/// response: `{"changed": true, "item": ...}` with a `causalContext`, or
+/// `{"changed": true, "item": null}` if it's been deleted.
+async fn channel_poll_result(deps: &Deps, user_id: &str, channel_id: &str) -> Result<ToolResult, McpError> {
+    let item = match deps.db.get_item(&format!("channel:{}", user_id), channel_id).await {
+        Ok(item) => item,
+        Err(e) => {
+            tracing::error!("channel-poll re-read: {}", e);
+            return Ok(error_result("failed to read channel"));
+        }
+    };
+
+    let result = match item {
+        Some(item) => {
+            let version = version_of(&item);
+            let mut obj: serde_json::Map<String, Value> = item.into_iter().collect();
+            obj.insert("causalContext".into(), Value::String(encode_context(&version)));
+            serde_json::json!({"changed": true, "item": Value::Object(obj)})
+        }
+        None => serde_json::json!({"changed": true, "item": null}),
+    };
+    Ok(text_result(&result.to_string()))
 }
 
 /// Validates the JWT token from tool arguments.
@@ -182,7 +841,7 @@ pub fn authenticate(deps: &Deps, args: &Value) -> Result<String, String> {
     if token.is_empty() {
         return Err("invalid or expired token".into());
     }
-    auth::parse_token(&deps.jwt_secret, token).map_err(|e| e.to_string())
+    auth::parse_token(&deps.jwt_keys, token).map_err(|e| e.to_string())
 }
 
 /// Generates a random alphanumeric string of the given length.
@@ -207,4 +866,30 @@ mod tests {
         assert_eq!(id.len(), 7);
         assert!(id.chars().all(|c| c.is_ascii_alphanumeric()));
     }
+
+    #[test]
+    fn test_causal_context_round_trips() {
+        let mut version = HashMap::new();
+        version.insert("alice".to_string(), 3u64);
+        let token = encode_context(&version);
+        assert_eq!(decode_context(&token), Some(version));
+    }
+
+    #[test]
+    fn test_dominates() {
+        let mut stored = HashMap::new();
+        stored.insert("alice".to_string(), 2u64);
+
+        let mut behind = HashMap::new();
+        behind.insert("alice".to_string(), 1u64);
+        assert!(!dominates(&behind, &stored));
+
+        let mut caught_up = HashMap::new();
+        caught_up.insert("alice".to_string(), 2u64);
+        assert!(dominates(&caught_up, &stored));
+
+        let mut ahead = HashMap::new();
+        ahead.insert("alice".to_string(), 3u64);
+        assert!(dominates(&ahead, &stored));
+    }
 }
@@ -1,32 +1,68 @@
 pub mod otp;
+pub mod totp;
+pub mod block;
 pub mod channel;
 pub mod channel_msg;
+pub mod channel_stream;
 pub mod subscription;
+pub mod subscription_pattern;
 pub mod webpush;
 pub mod account;
 
 use std::sync::Arc;
+use crate::auth::KeySet;
+use crate::cluster::{Broadcasting, ClusterApi, ClusterMetadata};
 use crate::dynamo::DynamoApi;
 use crate::notify::sns::SnsApi;
 use crate::notify::ses::SesApi;
 use crate::notify::webpush::WebPushKeys;
+use channel::ChannelChangeRegistry;
+use channel_stream::ChannelStreamRegistry;
 
 /// Shared dependencies for all tool handlers.
 pub struct Deps {
     pub db: Arc<dyn DynamoApi>,
-    pub jwt_secret: String,
+    /// Signing/verification keys for user auth tokens. A [`KeySet`] rather
+    /// than a bare secret so a key can be rotated in (and the outgoing one
+    /// retired) without invalidating every token issued under it.
+    pub jwt_keys: Arc<KeySet>,
     pub sns: Arc<dyn SnsApi>,
     pub ses: Arc<dyn SesApi>,
     pub ses_from_email: String,
     pub web_push_keys: WebPushKeys,
+    pub channel_streams: Arc<ChannelStreamRegistry>,
+    /// Per-channel change signals for `channel-poll`'s long-poll wait,
+    /// separate from `channel_streams` (message fan-out) since a poller
+    /// cares about the channel item's own version, not its messages.
+    pub channel_versions: Arc<ChannelChangeRegistry>,
+    /// Numeric-id push subscriptions for `channel-subscribe`, delivering
+    /// `notifications/message` frames to whichever sink is live on this
+    /// node for a given `(userId, subscriptionId)` pair.
+    pub notifications: Arc<mcpserver::NotificationRegistry>,
+    /// Channel-ID -> owning-node allocation. Empty/default when this
+    /// process isn't part of a cluster, so every channel is local.
+    pub cluster_metadata: Arc<ClusterMetadata>,
+    /// Node-to-node transport, used when a channel is owned remotely.
+    pub cluster: Arc<dyn ClusterApi>,
+    /// Remote nodes with a live local subscriber, keyed by channel — only
+    /// meaningful on the node that owns the channel.
+    pub broadcasting: Arc<Broadcasting>,
+    /// Bearer token expected on incoming `/cluster/*` requests. Empty
+    /// rejects all such requests, so an unconfigured node is not an open
+    /// relay by default.
+    pub cluster_shared_secret: String,
 }
 
 /// Register all tool handlers on the given MCP server.
 pub fn register_all(srv: &mut mcpserver::Server, deps: Arc<Deps>) {
     otp::register(srv, deps.clone());
+    totp::register(srv, deps.clone());
+    block::register(srv, deps.clone());
     channel::register(srv, deps.clone());
     channel_msg::register(srv, deps.clone());
+    channel_stream::register(srv, deps.clone());
     subscription::register(srv, deps.clone());
+    subscription_pattern::register(srv, deps.clone());
     webpush::register(srv, deps.clone());
     account::register(srv, deps);
 }
@@ -35,6 +71,7 @@ pub fn register_all(srv: &mut mcpserver::Server, deps: Arc<Deps>) {
 mod tests {
     use super::*;
     use crate::auth;
+    use crate::cluster::mock::MockCluster;
     use crate::dynamo::mock::MockDynamo;
     use crate::notify::sns::mock::MockSns;
     use crate::notify::ses::mock::MockSes;
@@ -43,8 +80,12 @@ mod tests {
 
     const TEST_SECRET: &str = "test-secret-key-for-hmac256";
 
+    fn test_keys() -> Arc<KeySet> {
+        Arc::new(KeySet::single_hs256("test", TEST_SECRET))
+    }
+
     fn test_token(user_id: &str) -> String {
-        auth::create_token(TEST_SECRET, user_id, 3600).unwrap()
+        auth::create_token(&test_keys(), user_id, 3600).unwrap()
     }
 
     fn setup_deps() -> (Arc<Deps>, Arc<MockDynamo>, Arc<MockSns>, Arc<MockSes>) {
@@ -54,11 +95,18 @@ mod tests {
 
         let deps = Arc::new(Deps {
             db: db.clone(),
-            jwt_secret: TEST_SECRET.into(),
+            jwt_keys: test_keys(),
             sns: sns.clone(),
             ses: ses.clone(),
             ses_from_email: "noreply@example.com".into(),
             web_push_keys: WebPushKeys::default(),
+            channel_streams: Arc::new(ChannelStreamRegistry::new()),
+            channel_versions: Arc::new(ChannelChangeRegistry::new()),
+            notifications: Arc::new(mcpserver::NotificationRegistry::new()),
+            cluster_metadata: Arc::new(ClusterMetadata::default()),
+            cluster: Arc::new(MockCluster::new()),
+            broadcasting: Arc::new(Broadcasting::new()),
+            cluster_shared_secret: "test-cluster-secret".into(),
         });
 
         (deps, db, sns, ses)
@@ -183,7 +231,7 @@ mod tests {
         // Should return a JWT token.
         let token_str = result.as_str().unwrap();
         assert!(!token_str.is_empty());
-        let user_id = auth::parse_token(TEST_SECRET, token_str).unwrap();
+        let user_id = auth::parse_token(&test_keys(), token_str).unwrap();
         assert_eq!(user_id, "+15551234567");
 
         // OTP should be deleted.
@@ -204,6 +252,60 @@ mod tests {
         assert!(result.as_str().unwrap().contains("invalid"));
     }
 
+    // ─── TOTP Tests ───
+
+    #[tokio::test]
+    async fn test_totp_register_and_verify() {
+        let (deps, db, _sns, _ses) = setup_deps();
+        let srv = setup_server(deps);
+
+        let result = call_tool(&srv, "totp-register", json!({
+            "phone": "+15551234567"
+        })).await;
+        let secret = result.get("secret").unwrap().as_str().unwrap().to_string();
+        assert!(result.get("uri").unwrap().as_str().unwrap().starts_with("otpauth://totp/"));
+
+        // Compute the current code the same way the handler does, using the
+        // stored secret directly (bypassing SNS delivery, unlike OTP).
+        let item = db.get_item("totp:+15551234567", "secret").await.unwrap().unwrap();
+        assert_eq!(item.get("secret").unwrap().as_str().unwrap(), secret);
+
+        let secret_bytes = base32::decode(base32::Alphabet::RFC4648 { padding: false }, &secret).unwrap();
+        let step = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            / 30;
+        let code = format!("{:06}", totp::totp_code_for_test(&secret_bytes, step));
+
+        let result = call_tool(&srv, "totp-verify", json!({
+            "phone": "+15551234567",
+            "code": code,
+        })).await;
+        let token_str = result.as_str().unwrap();
+        let user_id = auth::parse_token(&test_keys(), token_str).unwrap();
+        assert_eq!(user_id, "+15551234567");
+
+        // The same code+step can't be replayed.
+        let result = call_tool(&srv, "totp-verify", json!({
+            "phone": "+15551234567",
+            "code": code,
+        })).await;
+        assert!(result.as_str().unwrap().contains("already used"));
+    }
+
+    #[tokio::test]
+    async fn test_totp_verify_unregistered() {
+        let (deps, _db, _sns, _ses) = setup_deps();
+        let srv = setup_server(deps);
+
+        let result = call_tool(&srv, "totp-verify", json!({
+            "phone": "+15551234567",
+            "code": "123456",
+        })).await;
+        assert!(result.as_str().unwrap().contains("not registered"));
+    }
+
     // ─── Channel Tests ───
 
     #[tokio::test]
@@ -524,8 +626,72 @@ mod tests {
             "token": token,
             "channel": channel_id,
         })).await;
-        let items = result.as_array().unwrap();
-        assert_eq!(items.len(), 2);
+        let messages = result.get("messages").unwrap().as_array().unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0]["message"], "First msg");
+        assert_eq!(messages[1]["message"], "Second msg");
+        assert_eq!(result.get("hasMore").unwrap().as_bool().unwrap(), false);
+    }
+
+    #[tokio::test]
+    async fn test_channel_messages_pagination() {
+        let (deps, _db, _sns, _ses) = setup_deps();
+        let srv = setup_server(deps);
+        let token = test_token("user1");
+
+        let result = call_tool(&srv, "channel-put", json!({
+            "token": token,
+            "channel": "",
+            "name": "Paging Test",
+            "category": "food",
+            "poster": "owner"
+        })).await;
+        let channel_id = result.get("channelId").unwrap().as_str().unwrap().to_string();
+
+        for msg in &["m1", "m2", "m3", "m4"] {
+            call_tool(&srv, "channel-notify", json!({
+                "token": token,
+                "channel": channel_id,
+                "message": msg
+            })).await;
+        }
+
+        // `latest` scans backward with no anchor: the newest `limit`
+        // messages, returned in ascending order.
+        let latest = call_tool(&srv, "channel-messages", json!({
+            "token": token,
+            "channel": channel_id,
+            "mode": "latest",
+            "limit": 2,
+        })).await;
+        let latest_messages = latest.get("messages").unwrap().as_array().unwrap();
+        assert_eq!(latest_messages.len(), 2);
+        assert_eq!(latest_messages[0]["message"], "m3");
+        assert_eq!(latest_messages[1]["message"], "m4");
+        assert_eq!(latest.get("hasMore").unwrap().as_bool().unwrap(), true);
+
+        let cursor = latest_messages[0]["SK"].as_str().unwrap().to_string();
+
+        // `before` the first returned message should surface the older ones.
+        let before = call_tool(&srv, "channel-messages", json!({
+            "token": token,
+            "channel": channel_id,
+            "mode": "before",
+            "cursor": cursor,
+        })).await;
+        let before_messages = before.get("messages").unwrap().as_array().unwrap();
+        assert_eq!(before_messages.len(), 2);
+        assert_eq!(before_messages[0]["message"], "m1");
+        assert_eq!(before_messages[1]["message"], "m2");
+
+        // An unknown cursor is rejected.
+        let bad = call_tool(&srv, "channel-messages", json!({
+            "token": token,
+            "channel": channel_id,
+            "mode": "after",
+            "cursor": "nonexistent",
+        })).await;
+        assert!(is_error(&bad));
     }
 
     #[tokio::test]
@@ -542,6 +708,104 @@ mod tests {
         assert!(is_error(&result));
     }
 
+    // ─── Block Tests ───
+
+    #[tokio::test]
+    async fn test_block_add_and_list() {
+        let (deps, _db, _sns, _ses) = setup_deps();
+        let srv = setup_server(deps);
+        let token1 = test_token("user1");
+
+        let result = call_tool(&srv, "block-add", json!({
+            "token": token1,
+            "user": "user2",
+        })).await;
+        assert_eq!(result.as_str().unwrap(), "blocked");
+
+        let result = call_tool(&srv, "blocks-list", json!({
+            "token": token1,
+        })).await;
+        let items = result.as_array().unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0]["SK"], "user2");
+    }
+
+    #[tokio::test]
+    async fn test_block_remove() {
+        let (deps, _db, _sns, _ses) = setup_deps();
+        let srv = setup_server(deps);
+        let token1 = test_token("user1");
+
+        call_tool(&srv, "block-add", json!({
+            "token": token1,
+            "user": "user2",
+        })).await;
+
+        let result = call_tool(&srv, "block-remove", json!({
+            "token": token1,
+            "user": "user2",
+        })).await;
+        assert_eq!(result.as_str().unwrap(), "unblocked");
+
+        let result = call_tool(&srv, "blocks-list", json!({
+            "token": token1,
+        })).await;
+        assert_eq!(result.as_array().unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_channel_messages_filters_blocked_sender_bidirectionally() {
+        let (deps, _db, _sns, _ses) = setup_deps();
+        let srv = setup_server(deps);
+        let token1 = test_token("user1");
+        let token2 = test_token("user2");
+
+        let result = call_tool(&srv, "channel-put", json!({
+            "token": token1,
+            "channel": "",
+            "name": "Block Test",
+            "category": "food",
+            "poster": "owner"
+        })).await;
+        let channel_id = result.get("channelId").unwrap().as_str().unwrap().to_string();
+
+        // user1 blocks user2.
+        call_tool(&srv, "block-add", json!({
+            "token": token1,
+            "user": "user2",
+        })).await;
+
+        call_tool(&srv, "channel-notify", json!({
+            "token": token1,
+            "channel": channel_id,
+            "message": "from user1"
+        })).await;
+        call_tool(&srv, "channel-notify", json!({
+            "token": token2,
+            "channel": channel_id,
+            "message": "from user2"
+        })).await;
+
+        // user1 (the blocker) shouldn't see user2's message.
+        let result = call_tool(&srv, "channel-messages", json!({
+            "token": token1,
+            "channel": channel_id,
+        })).await;
+        let messages = result.get("messages").unwrap().as_array().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["message"], "from user1");
+
+        // user2 (blocked) shouldn't see user1's message either — blocks are
+        // bidirectional for channel visibility.
+        let result = call_tool(&srv, "channel-messages", json!({
+            "token": token2,
+            "channel": channel_id,
+        })).await;
+        let messages = result.get("messages").unwrap().as_array().unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0]["message"], "from user2");
+    }
+
     // ─── WebPush Tests ───
 
     #[tokio::test]
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use mcpserver::{text_result, error_result, FnToolHandler, ToolResult, McpError};
+use serde_json::Value;
+
+use super::Deps;
+use super::channel::authenticate;
+
+pub fn register(srv: &mut mcpserver::Server, deps: Arc<Deps>) {
+    let d = deps.clone();
+    srv.handle_tool("block-add", FnToolHandler::new(move |args: Value| {
+        let deps = d.clone();
+        async move { handle_block_add(&deps, args).await }
+    }));
+
+    let d = deps.clone();
+    srv.handle_tool("block-remove", FnToolHandler::new(move |args: Value| {
+        let deps = d.clone();
+        async move { handle_block_remove(&deps, args).await }
+    }));
+
+    let d = deps;
+    srv.handle_tool("blocks-list", FnToolHandler::new(move |args: Value| {
+        let deps = d.clone();
+        async move { handle_blocks_list(&deps, args).await }
+    }));
+}
+
+async fn handle_block_add(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
+    let user_id = match authenticate(deps, &args) {
+        Ok(id) => id,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+
+    let blocked_id = args.get("user").and_then(|v| v.as_str()).unwrap_or("");
+    if blocked_id.is_empty() {
+        return Ok(error_result("user required"));
+    }
+    if blocked_id == user_id {
+        return Ok(error_result("cannot block yourself"));
+    }
+
+    if let Err(e) = deps.db.put_item(
+        &format!("block:{}", user_id),
+        blocked_id,
+        "", "", "", "",
+        HashMap::new(),
+    ).await {
+        tracing::error!("block-add: {}", e);
+        return Ok(error_result("failed to block user"));
+    }
+
+    Ok(text_result("blocked"))
+}
+
+async fn handle_block_remove(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
+    let user_id = match authenticate(deps, &args) {
+        Ok(id) => id,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+
+    let blocked_id = args.get("user").and_then(|v| v.as_str()).unwrap_or("");
+    if blocked_id.is_empty() {
+        return Ok(error_result("user required"));
+    }
+
+    if let Err(e) = deps.db.delete_item(&format!("block:{}", user_id), blocked_id).await {
+        tracing::error!("block-remove: {}", e);
+        return Ok(error_result("failed to unblock user"));
+    }
+
+    Ok(text_result("unblocked"))
+}
+
+async fn handle_blocks_list(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
+    let user_id = match authenticate(deps, &args) {
+        Ok(id) => id,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+
+    let items = match deps.db.query(&format!("block:{}", user_id)).await {
+        Ok(items) => items,
+        Err(e) => {
+            tracing::error!("blocks-list: {}", e);
+            return Ok(error_result("failed to list blocks"));
+        }
+    };
+
+    let buf = serde_json::to_string(&items).unwrap_or_else(|_| "[]".into());
+    Ok(text_result(&buf))
+}
+
+/// True if `a` and `b` have blocked each other in either direction.
+///
+/// Channel visibility treats a block as bidirectional — stricter than a
+/// one-way mute — so a blocked user also can't see the blocker's messages.
+pub async fn blocked_either_way(deps: &Deps, a: &str, b: &str) -> bool {
+    if a.is_empty() || b.is_empty() || a == b {
+        return false;
+    }
+    let a_blocks_b = deps.db.get_item(&format!("block:{}", a), b).await.ok().flatten().is_some();
+    if a_blocks_b {
+        return true;
+    }
+    deps.db.get_item(&format!("block:{}", b), a).await.ok().flatten().is_some()
+}
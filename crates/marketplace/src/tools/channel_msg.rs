@@ -5,9 +5,15 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use mcpserver::{text_result, error_result, FnToolHandler, ToolResult, McpError};
 use serde_json::Value;
 
+use crate::dynamo::ScanDirection;
 use crate::notify::webpush;
 use super::Deps;
+use super::block;
 use super::channel::authenticate;
+use super::channel_stream::ChannelEvent;
+
+/// Default page size for `channel-messages` when `limit` isn't given.
+const DEFAULT_PAGE_SIZE: usize = 50;
 
 pub fn register(srv: &mut mcpserver::Server, deps: Arc<Deps>) {
     let d = deps.clone();
@@ -36,6 +42,42 @@ async fn handle_channel_notify(deps: &Deps, args: Value) -> Result<ToolResult, M
         return Ok(error_result("channel and message required"));
     }
 
+    // `fallback`: if a subscriber has no live push endpoint, fall back to
+    // SMS/email delivery at their account identifier (phone or email — the
+    // same identifier `otp-verify` authenticated them with).
+    let fallback = args.get("fallback").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    // A channel owned by another cluster node doesn't persist or fan out
+    // here — forward to the owner, which runs this same logic on receipt.
+    if let Some(owner) = deps.cluster_metadata.owner_of(channel_id) {
+        if owner != deps.cluster_metadata.node_id {
+            return Ok(
+                match deps.cluster.forward_notify(owner, channel_id, &user_id, message, fallback).await {
+                    Ok(()) => text_result(
+                        &serde_json::json!({"stored": true, "forwardedTo": owner}).to_string(),
+                    ),
+                    Err(e) => {
+                        tracing::error!("channel-notify forward to owner {}: {}", owner, e);
+                        error_result("failed to forward to owning node")
+                    }
+                },
+            );
+        }
+    }
+
+    persist_and_fan_out(deps, channel_id, &user_id, message, fallback).await
+}
+
+/// Persist `message` to `channel_id` and fan it out locally. Only ever runs
+/// on the channel's owning node — whether the call originated from a local
+/// client or was forwarded here from another node via `/cluster/notify`.
+pub async fn persist_and_fan_out(
+    deps: &Deps,
+    channel_id: &str,
+    user_id: &str,
+    message: &str,
+    fallback: bool,
+) -> Result<ToolResult, McpError> {
     // Store the message in DynamoDB.
     // Use nanosecond precision to avoid collisions in rapid succession.
     let ts = SystemTime::now()
@@ -45,7 +87,7 @@ async fn handle_channel_notify(deps: &Deps, args: Value) -> Result<ToolResult, M
         .to_string();
 
     let mut attrs = HashMap::new();
-    attrs.insert("sender".into(), Value::String(user_id.clone()));
+    attrs.insert("sender".into(), Value::String(user_id.into()));
     attrs.insert("message".into(), Value::String(message.into()));
 
     if let Err(e) = deps.db.put_item(
@@ -58,6 +100,16 @@ async fn handle_channel_notify(deps: &Deps, args: Value) -> Result<ToolResult, M
         return Ok(error_result("failed to store message"));
     }
 
+    // Push the new message to any live channel-stream subscribers — this
+    // runs alongside the web-push fan-out below, not instead of it.
+    let event = ChannelEvent::MessageCreated {
+        channel: channel_id.to_string(),
+        sender: user_id.to_string(),
+        message: message.to_string(),
+        ts: ts.clone(),
+    };
+    deps.channel_streams.publish(channel_id, event.clone());
+
     // Fan-out: find all subscribers to this channel via GSI1.
     let subs = match deps.db.query_gsi_with_sk("GSI1", "subscription", channel_id).await {
         Ok(s) => s,
@@ -67,8 +119,16 @@ async fn handle_channel_notify(deps: &Deps, args: Value) -> Result<ToolResult, M
         }
     };
 
+    let payload = serde_json::json!({
+        "channel": channel_id,
+        "message": message,
+        "sender": user_id,
+    });
+
     // For each subscriber, find their web-push subscriptions and send.
     let mut push_count: usize = 0;
+    let mut fallback_count: usize = 0;
+    let mut deliveries = Vec::with_capacity(subs.len());
     for sub in &subs {
         let sub_pk = match sub.get("PK").and_then(|v| v.as_str()) {
             Some(pk) => pk,
@@ -78,6 +138,20 @@ async fn handle_channel_notify(deps: &Deps, args: Value) -> Result<ToolResult, M
         // Extract subscriber userId from PK ("subscription:{userId}").
         let subscriber_id = &sub_pk["subscription:".len()..];
 
+        // Push straight to this subscriber's live `channel-subscribe`
+        // connection, if this node is the one holding its sink — a no-op
+        // if they subscribed from a different node, or aren't connected
+        // right now.
+        if let Some(subscription_id) = sub
+            .get("subscriptionId")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<u64>().ok())
+        {
+            deps.notifications
+                .notify(subscriber_id, subscription_id, "notifications/message", payload.clone())
+                .await;
+        }
+
         // Get web-push subscriptions for this subscriber.
         let push_subs = match deps.db.query(&format!("web-push:{}", subscriber_id)).await {
             Ok(ps) => ps,
@@ -87,51 +161,258 @@ async fn handle_channel_notify(deps: &Deps, args: Value) -> Result<ToolResult, M
             }
         };
 
-        let payload = serde_json::json!({
-            "channel": channel_id,
-            "message": message,
-            "sender": user_id,
-        });
-
+        let mut subscriber_push_sent = 0;
         for ps in &push_subs {
             let sub_json = match ps.get("subscription").and_then(|v| v.as_str()) {
                 Some(s) => s,
                 None => continue,
             };
-            if let Err(e) = webpush::send_web_push(sub_json, &payload, &deps.web_push_keys) {
-                tracing::error!("web-push send subscriber={}: {}", subscriber_id, e);
-            } else {
-                push_count += 1;
+            match webpush::send_web_push(sub_json, &payload, &deps.web_push_keys).await {
+                Ok(()) => {
+                    push_count += 1;
+                    subscriber_push_sent += 1;
+                }
+                Err(webpush::WebPushError::Gone(status)) => {
+                    tracing::info!(
+                        "web-push subscriber={} gone ({}), pruning subscription",
+                        subscriber_id,
+                        status
+                    );
+                    if let Some(sk) = ps.get("SK").and_then(|v| v.as_str()) {
+                        if let Err(e) = deps.db.delete_item(&format!("web-push:{}", subscriber_id), sk).await {
+                            tracing::error!("web-push prune subscriber={}: {}", subscriber_id, e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("web-push send subscriber={}: {}", subscriber_id, e);
+                }
+            }
+        }
+
+        let mut subscriber_fallback_sent = false;
+        if fallback && subscriber_push_sent == 0 {
+            if send_fallback(deps, subscriber_id, &payload).await {
+                fallback_count += 1;
+                subscriber_fallback_sent = true;
             }
         }
+
+        deliveries.push(serde_json::json!({
+            "subscriber": subscriber_id,
+            "pushSent": subscriber_push_sent,
+            "fallbackSent": subscriber_fallback_sent,
+        }));
+    }
+
+    // Forward to any other cluster nodes with a live local subscriber for
+    // this channel, so they can publish into their own `ChannelStreamRegistry`
+    // without re-persisting or re-fanning-out themselves.
+    let event_json = serde_json::to_value(&event).unwrap_or(Value::Null);
+    for node_id in deps.broadcasting.subscriber_nodes(channel_id) {
+        if let Err(e) = deps.cluster.forward_publish(&node_id, channel_id, &event_json).await {
+            tracing::error!("channel-notify forward_publish node={}: {}", node_id, e);
+        }
     }
 
     let result = serde_json::json!({
         "stored": true,
         "pushSent": push_count,
+        "fallbackSent": fallback_count,
         "subscribers": subs.len(),
+        "deliveries": deliveries,
     });
     Ok(text_result(&result.to_string()))
 }
 
+/// Fall back to SMS/email for a subscriber with no live push endpoint,
+/// delivering to whichever identifier they authenticated with (the same
+/// phone/email `otp-verify` issues their token for). Returns whether a
+/// fallback message was actually sent.
+async fn send_fallback(deps: &Deps, subscriber_id: &str, payload: &serde_json::Value) -> bool {
+    let message = match (payload.get("sender").and_then(|v| v.as_str()), payload.get("message").and_then(|v| v.as_str())) {
+        (Some(sender), Some(message)) => format!("{}: {}", sender, message),
+        _ => return false,
+    };
+
+    let sent = if subscriber_id.contains('@') {
+        deps.ses
+            .send_email(&deps.ses_from_email, subscriber_id, "New channel message", &message)
+            .await
+            .map_err(|e| tracing::error!("channel-notify fallback email subscriber={}: {}", subscriber_id, e))
+            .is_ok()
+    } else if !subscriber_id.is_empty() {
+        deps.sns
+            .send_sms(subscriber_id, &message)
+            .await
+            .map_err(|e| tracing::error!("channel-notify fallback sms subscriber={}: {}", subscriber_id, e))
+            .is_ok()
+    } else {
+        false
+    };
+
+    sent
+}
+
+/// `channel-messages` supports CHATHISTORY-style pagination via a `mode`
+/// argument: `latest` (default), `before`, `after`, and `around`, each
+/// combined with a `cursor` (the anchor message's SK, i.e. its `ts`) and an
+/// optional `limit`. Results always come back in stable ascending order
+/// with a `hasMore`/`nextCursor` pair so clients can keep paging backward
+/// through long histories.
+///
+/// `before`/`after` (nanosecond-timestamp cursors, matching the stored sort
+/// key) are accepted as shorthand for `mode`/`cursor` — `before: "<ts>"` is
+/// equivalent to `mode: "before", cursor: "<ts>"`, likewise for `after` —
+/// for callers doing a plain Garage-K2V-style range query that don't need
+/// `around`. `reverse` flips the `latest` page's scan direction (oldest
+/// page first instead of newest) when no cursor is given at all.
 async fn handle_channel_messages(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
-    if let Err(msg) = authenticate(deps, &args) {
-        return Ok(error_result(&msg));
-    }
+    let user_id = match authenticate(deps, &args) {
+        Ok(id) => id,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
 
     let channel_id = args.get("channel").and_then(|v| v.as_str()).unwrap_or("");
     if channel_id.is_empty() {
         return Ok(error_result("channel required"));
     }
 
-    let items = match deps.db.query(&format!("message:{}", channel_id)).await {
-        Ok(items) => items,
-        Err(e) => {
-            tracing::error!("channel-messages: {}", e);
-            return Ok(error_result("failed to list messages"));
+    let before = args.get("before").and_then(|v| v.as_str()).filter(|c| !c.is_empty());
+    let after = args.get("after").and_then(|v| v.as_str()).filter(|c| !c.is_empty());
+    let reverse = args.get("reverse").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    let (mode, cursor) = match args.get("mode").and_then(|v| v.as_str()) {
+        Some(mode) => (mode, args.get("cursor").and_then(|v| v.as_str()).filter(|c| !c.is_empty())),
+        None => match (before, after) {
+            (Some(ts), _) => ("before", Some(ts)),
+            (None, Some(ts)) => ("after", Some(ts)),
+            (None, None) if reverse => ("earliest", None),
+            (None, None) => ("latest", None),
+        },
+    };
+
+    let limit = args
+        .get("limit")
+        .and_then(|v| v.as_u64())
+        .map(|n| n as usize)
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_PAGE_SIZE);
+
+    let pk = format!("message:{}", channel_id);
+
+    if mode != "latest" && mode != "earliest" {
+        let anchor = match cursor {
+            Some(c) => c,
+            None => return Ok(error_result("cursor required for before/after/around")),
+        };
+        match deps.db.get_item(&pk, anchor).await {
+            Ok(Some(_)) => {}
+            Ok(None) => return Ok(error_result("cursor message not found in channel")),
+            Err(e) => {
+                tracing::error!("channel-messages anchor lookup: {}", e);
+                return Ok(error_result("failed to validate cursor"));
+            }
+        }
+    }
+
+    let (page, has_more) = match mode {
+        "before" => {
+            let items = match deps.db.query_range(&pk, cursor, ScanDirection::Backward, limit).await {
+                Ok(items) => items,
+                Err(e) => {
+                    tracing::error!("channel-messages before: {}", e);
+                    return Ok(error_result("failed to list messages"));
+                }
+            };
+            let has_more = items.len() == limit;
+            (sort_ascending(items), has_more)
+        }
+        "after" => {
+            let items = match deps.db.query_range(&pk, cursor, ScanDirection::Forward, limit).await {
+                Ok(items) => items,
+                Err(e) => {
+                    tracing::error!("channel-messages after: {}", e);
+                    return Ok(error_result("failed to list messages"));
+                }
+            };
+            let has_more = items.len() == limit;
+            (sort_ascending(items), has_more)
+        }
+        "around" => {
+            let half = (limit / 2).max(1);
+            let older = match deps.db.query_range(&pk, cursor, ScanDirection::Backward, half).await {
+                Ok(items) => items,
+                Err(e) => {
+                    tracing::error!("channel-messages around (older): {}", e);
+                    return Ok(error_result("failed to list messages"));
+                }
+            };
+            let newer = match deps.db.query_range(&pk, cursor, ScanDirection::Forward, half).await {
+                Ok(items) => items,
+                Err(e) => {
+                    tracing::error!("channel-messages around (newer): {}", e);
+                    return Ok(error_result("failed to list messages"));
+                }
+            };
+            let has_more = older.len() == half || newer.len() == half;
+
+            let anchor_item = deps.db.get_item(&pk, cursor.unwrap_or_default()).await.ok().flatten();
+            let mut merged = sort_ascending(older);
+            merged.extend(anchor_item);
+            merged.extend(sort_ascending(newer));
+            (merged, has_more)
+        }
+        "earliest" => {
+            let items = match deps.db.query_range(&pk, None, ScanDirection::Forward, limit).await {
+                Ok(items) => items,
+                Err(e) => {
+                    tracing::error!("channel-messages earliest: {}", e);
+                    return Ok(error_result("failed to list messages"));
+                }
+            };
+            let has_more = items.len() == limit;
+            (sort_ascending(items), has_more)
+        }
+        _ => {
+            let items = match deps.db.query_range(&pk, None, ScanDirection::Backward, limit).await {
+                Ok(items) => items,
+                Err(e) => {
+                    tracing::error!("channel-messages latest: {}", e);
+                    return Ok(error_result("failed to list messages"));
+                }
+            };
+            let has_more = items.len() == limit;
+            (sort_ascending(items), has_more)
         }
     };
 
-    let buf = serde_json::to_string(&items).unwrap_or_else(|_| "[]".into());
-    Ok(text_result(&buf))
+    // The cursor follows the underlying store's order regardless of
+    // block-filtering below, so paging keeps working across filtered pages.
+    let next_cursor = page.first().map(sk_of);
+
+    let mut messages = Vec::with_capacity(page.len());
+    for item in page {
+        let sender = item.get("sender").and_then(|v| v.as_str()).unwrap_or("").to_string();
+        if sender.is_empty() || !block::blocked_either_way(deps, &user_id, &sender).await {
+            messages.push(item);
+        }
+    }
+
+    let result = serde_json::json!({
+        "messages": messages,
+        "hasMore": has_more,
+        "nextCursor": next_cursor,
+    });
+    Ok(text_result(&result.to_string()))
+}
+
+/// The message's sort key (its `ts`), used as the page's pagination cursor.
+fn sk_of(item: &HashMap<String, Value>) -> String {
+    item.get("SK").and_then(|v| v.as_str()).unwrap_or_default().to_string()
+}
+
+fn sort_ascending(mut items: Vec<HashMap<String, Value>>) -> Vec<HashMap<String, Value>> {
+    items.sort_by(|a, b| sk_of(a).cmp(&sk_of(b)));
+    items
 }
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use mcpserver::{error_result, text_result, FnToolHandler, McpError, ToolResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::broadcast;
+
+use super::block;
+use super::channel::authenticate;
+use super::Deps;
+
+/// Backlog of a new subscriber's broadcast receiver before it starts
+/// missing events under load.
+const CHANNEL_CAPACITY: usize = 256;
+
+/// An event published to a channel's live subscribers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum ChannelEvent {
+    MessageCreated {
+        channel: String,
+        sender: String,
+        message: String,
+        ts: String,
+    },
+    MessageDeleted {
+        channel: String,
+        ts: String,
+    },
+    ChannelDeleted {
+        channel: String,
+    },
+}
+
+/// In-process fan-out registry: one `broadcast` channel per live channel id,
+/// created lazily on first subscribe.
+///
+/// Lives on `Deps` so `channel-notify` (in `channel_msg`) can publish to it
+/// after persisting, and `channel-stream` can subscribe to it.
+#[derive(Default)]
+pub struct ChannelStreamRegistry {
+    channels: DashMap<String, broadcast::Sender<ChannelEvent>>,
+}
+
+impl ChannelStreamRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribe to `channel_id`, creating its broadcast channel if this is
+    /// the first subscriber.
+    pub fn subscribe(&self, channel_id: &str) -> broadcast::Receiver<ChannelEvent> {
+        self.channels
+            .entry(channel_id.to_string())
+            .or_insert_with(|| broadcast::channel(CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    /// Publish `event` to `channel_id`'s subscribers, if any are live.
+    ///
+    /// A send failure just means every receiver has dropped — evict the
+    /// now-useless sender instead of keeping it (and the channel id) around
+    /// forever.
+    pub fn publish(&self, channel_id: &str, event: ChannelEvent) {
+        let stale = match self.channels.get(channel_id) {
+            Some(tx) => tx.send(event).is_err(),
+            None => false,
+        };
+        if stale {
+            self.channels.remove(channel_id);
+        }
+    }
+}
+
+pub fn register(srv: &mut mcpserver::Server, deps: Arc<Deps>) {
+    let d = deps;
+    srv.handle_tool(
+        "channel-stream",
+        FnToolHandler::new(move |args: Value| {
+            let deps = d.clone();
+            async move { handle_channel_stream(&deps, args).await }
+        }),
+    );
+}
+
+/// Wait for and return the next [`ChannelEvent`] on `channel`. This crate's
+/// tools are request/response, not a persistent connection, so a streaming
+/// transport (SSE/long-poll) should call this tool in a loop to drain the
+/// channel's stream of `type`-tagged events.
+async fn handle_channel_stream(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
+    let user_id = match authenticate(deps, &args) {
+        Ok(id) => id,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+
+    let channel_id = args.get("channel").and_then(|v| v.as_str()).unwrap_or("");
+    if channel_id.is_empty() {
+        return Ok(error_result("channel required"));
+    }
+
+    let mut rx = deps.channel_streams.subscribe(channel_id);
+    loop {
+        let event = match rx.recv().await {
+            Ok(event) => event,
+            Err(_) => return Ok(error_result("channel stream closed")),
+        };
+
+        // A block is bidirectional for channel visibility, so skip (rather
+        // than surface) events authored by anyone blocked either way.
+        if let ChannelEvent::MessageCreated { sender, .. } = &event {
+            if block::blocked_either_way(deps, &user_id, sender).await {
+                continue;
+            }
+        }
+
+        return Ok(text_result(
+            serde_json::to_string(&event).unwrap_or_else(|_| "{}".into()),
+        ));
+    }
+}
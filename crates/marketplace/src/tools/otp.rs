@@ -103,7 +103,7 @@ async fn handle_otp_verify(deps: &Deps, args: Value) -> Result<ToolResult, McpEr
     let _ = deps.db.delete_item(&format!("otp:{}", dest), code).await;
 
     // Create JWT — userId derived from destination.
-    let token = match auth::create_token(&deps.jwt_secret, dest, 86400) {
+    let token = match auth::create_token(&deps.jwt_keys, dest, 86400) {
         Ok(t) => t,
         Err(e) => {
             tracing::error!("otp create token: {}", e);
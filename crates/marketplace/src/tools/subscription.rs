@@ -20,11 +20,17 @@ pub fn register(srv: &mut mcpserver::Server, deps: Arc<Deps>) {
         async move { handle_channel_unsubscribe(&deps, args).await }
     }));
 
-    let d = deps;
+    let d = deps.clone();
     srv.handle_tool("subscriptions-list", FnToolHandler::new(move |args: Value| {
         let deps = d.clone();
         async move { handle_subscriptions_list(&deps, args).await }
     }));
+
+    let d = deps;
+    srv.handle_tool("channel-resubscribe", FnToolHandler::new(move |args: Value| {
+        let deps = d.clone();
+        async move { handle_channel_resubscribe(&deps, args).await }
+    }));
 }
 
 async fn handle_channel_subscribe(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
@@ -51,9 +57,17 @@ async fn handle_channel_subscribe(deps: &Deps, args: Value) -> Result<ToolResult
         return Ok(error_result("channel not found"));
     }
 
+    // Allocate a numeric push-notification subscription id for this
+    // (user, channel) pair and stash it on the row, so `channel-notify`'s
+    // fan-out can address this node's live `NotificationRegistry` sink
+    // without this subscriber needing to hold any connection open across
+    // the `channel-subscribe` call itself.
+    let (subscription_id, _receiver) = deps.notifications.subscribe(&user_id);
+
     // Create subscription.
     let mut attrs = HashMap::new();
     attrs.insert("subscribedAt".into(), Value::String(channel_id.into()));
+    attrs.insert("subscriptionId".into(), Value::String(subscription_id.to_string()));
 
     if let Err(e) = deps.db.put_item(
         &format!("subscription:{}", user_id),
@@ -64,10 +78,25 @@ async fn handle_channel_subscribe(deps: &Deps, args: Value) -> Result<ToolResult
         attrs,
     ).await {
         tracing::error!("channel-subscribe put: {}", e);
+        deps.notifications.unsubscribe(&user_id, subscription_id);
         return Ok(error_result("failed to subscribe"));
     }
 
-    Ok(text_result("subscribed"))
+    // If another node owns this channel, tell it so future `channel-notify`
+    // calls there get forwarded back to us for this node's subscribers.
+    if let Some(owner) = deps.cluster_metadata.owner_of(channel_id) {
+        if owner != deps.cluster_metadata.node_id {
+            if let Err(e) = deps
+                .cluster
+                .register_subscription(owner, channel_id, &deps.cluster_metadata.node_id)
+                .await
+            {
+                tracing::error!("channel-subscribe register with owner {}: {}", owner, e);
+            }
+        }
+    }
+
+    Ok(text_result(&serde_json::json!({"subscriptionId": subscription_id}).to_string()))
 }
 
 async fn handle_channel_unsubscribe(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
@@ -81,6 +110,18 @@ async fn handle_channel_unsubscribe(deps: &Deps, args: Value) -> Result<ToolResu
         return Ok(error_result("channel required"));
     }
 
+    // Drop the local push sink first, if this node is the one holding it —
+    // looked up by the id we stashed on the row at subscribe time.
+    if let Ok(Some(item)) = deps.db.get_item(&format!("subscription:{}", user_id), channel_id).await {
+        if let Some(subscription_id) = item
+            .get("subscriptionId")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<u64>().ok())
+        {
+            deps.notifications.unsubscribe(&user_id, subscription_id);
+        }
+    }
+
     if let Err(e) = deps.db.delete_item(&format!("subscription:{}", user_id), channel_id).await {
         tracing::error!("channel-unsubscribe: {}", e);
         return Ok(error_result("failed to unsubscribe"));
@@ -106,3 +147,88 @@ async fn handle_subscriptions_list(deps: &Deps, args: Value) -> Result<ToolResul
     let buf = serde_json::to_string(&items).unwrap_or_else(|_| "[]".into());
     Ok(text_result(&buf))
 }
+
+/// Call after a streaming transport reconnects: re-materializes every
+/// `subscription:{user_id}` row instead of forcing the client to
+/// `channel-subscribe` all over again, and replays whatever notifications
+/// it missed while disconnected.
+///
+/// `args.lastEventIds` is an optional `{channel: eventId}` map of the last
+/// event id the client saw per channel; omitted or unknown channels replay
+/// everything still buffered. A subscription this node's
+/// `NotificationRegistry` still has live (the common case — same node,
+/// just a dropped socket) resumes in place under its existing id; one that
+/// isn't tracked here any more (process restart, or it lived on a
+/// different node) is re-subscribed fresh and the row updated with the new
+/// id, so it's never registered twice.
+async fn handle_channel_resubscribe(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
+    let user_id = match authenticate(deps, &args) {
+        Ok(id) => id,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+
+    let last_event_ids = args.get("lastEventIds").and_then(|v| v.as_object()).cloned().unwrap_or_default();
+
+    let items = match deps.db.query(&format!("subscription:{}", user_id)).await {
+        Ok(items) => items,
+        Err(e) => {
+            tracing::error!("channel-resubscribe query: {}", e);
+            return Ok(error_result("failed to list subscriptions"));
+        }
+    };
+
+    let mut resumed = Vec::with_capacity(items.len());
+    let mut replayed = Vec::new();
+
+    for item in items {
+        let channel_id = match item.get("SK").and_then(|v| v.as_str()) {
+            Some(c) => c.to_string(),
+            None => continue,
+        };
+        let stored_id = item
+            .get("subscriptionId")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<u64>().ok());
+        let since = last_event_ids.get(&channel_id).and_then(|v| v.as_u64()).unwrap_or(0);
+
+        let subscription_id = match stored_id.and_then(|id| deps.notifications.resume(&user_id, id, since).map(|r| (id, r))) {
+            Some((id, (_receiver, frames))) => {
+                for frame in frames {
+                    replayed.push(serde_json::json!({
+                        "channel": channel_id,
+                        "eventId": frame.event_id,
+                        "result": frame.params["result"],
+                    }));
+                }
+                id
+            }
+            None => {
+                // Not tracked locally any more — re-materialize fresh and
+                // persist the new id so future `channel-notify` fan-out
+                // addresses it.
+                let (new_id, _receiver) = deps.notifications.subscribe(&user_id);
+                let mut attrs = HashMap::new();
+                attrs.insert(
+                    "subscribedAt".into(),
+                    item.get("subscribedAt")
+                        .cloned()
+                        .unwrap_or_else(|| Value::String(channel_id.clone())),
+                );
+                attrs.insert("subscriptionId".into(), Value::String(new_id.to_string()));
+                if let Err(e) = deps
+                    .db
+                    .put_item(&format!("subscription:{}", user_id), &channel_id, "subscription", &channel_id, "", "", attrs)
+                    .await
+                {
+                    tracing::error!("channel-resubscribe re-persist channel={}: {}", channel_id, e);
+                }
+                new_id
+            }
+        };
+
+        resumed.push(serde_json::json!({"channel": channel_id, "subscriptionId": subscription_id}));
+    }
+
+    let result = serde_json::json!({"subscriptions": resumed, "replayed": replayed});
+    Ok(text_result(&result.to_string()))
+}
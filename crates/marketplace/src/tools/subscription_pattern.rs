@@ -0,0 +1,272 @@
+use std::sync::Arc;
+use std::collections::HashMap;
+
+use mcpserver::{text_result, error_result, FnToolHandler, ToolResult, McpError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::notify::webpush;
+use super::Deps;
+use super::channel::authenticate;
+
+/// One field of a stored [`Pattern`] — a partial item template matched
+/// against a DynamoDB Streams `NEW_IMAGE`. Fields the pattern doesn't
+/// mention are ignored, so a subscription only constrains what it
+/// actually cares about.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PatternValue {
+    /// Matches only a field structurally equal to `value` (`serde_json`
+    /// already compares arrays/objects recursively, so this covers
+    /// nested `L`/`M` fields too).
+    Literal { value: Value },
+    /// Matches any value, including the field being entirely absent.
+    Wildcard,
+    /// Matches any present value and binds it under `name` in the
+    /// notification payload delivered to the matched subscriber.
+    Capture { name: String },
+}
+
+pub type Pattern = HashMap<String, PatternValue>;
+
+pub fn register(srv: &mut mcpserver::Server, deps: Arc<Deps>) {
+    let d = deps.clone();
+    srv.handle_tool("pattern-subscribe", FnToolHandler::new(move |args: Value| {
+        let deps = d.clone();
+        async move { handle_pattern_subscribe(&deps, args).await }
+    }));
+
+    let d = deps;
+    srv.handle_tool("pattern-unsubscribe", FnToolHandler::new(move |args: Value| {
+        let deps = d.clone();
+        async move { handle_pattern_unsubscribe(&deps, args).await }
+    }));
+}
+
+async fn handle_pattern_subscribe(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
+    let user_id = match authenticate(deps, &args) {
+        Ok(id) => id,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+
+    let topic = args.get("topic").and_then(|v| v.as_str()).unwrap_or("");
+    if topic.is_empty() {
+        return Ok(error_result("topic required"));
+    }
+
+    let pattern: Pattern = match args.get("pattern").cloned() {
+        Some(raw) => match serde_json::from_value(raw) {
+            Ok(p) => p,
+            Err(e) => return Ok(error_result(&format!("invalid pattern: {}", e))),
+        },
+        None => return Ok(error_result("pattern required")),
+    };
+
+    let mut attrs = HashMap::new();
+    attrs.insert("pattern".into(), serde_json::to_value(&pattern).unwrap_or(Value::Null));
+
+    if let Err(e) = deps.db.put_item(
+        &format!("sub:{}", topic),
+        &user_id,
+        "", "", "", "",
+        attrs,
+    ).await {
+        tracing::error!("pattern-subscribe put: {}", e);
+        return Ok(error_result("failed to subscribe"));
+    }
+
+    Ok(text_result(&serde_json::json!({"subscribed": true}).to_string()))
+}
+
+async fn handle_pattern_unsubscribe(deps: &Deps, args: Value) -> Result<ToolResult, McpError> {
+    let user_id = match authenticate(deps, &args) {
+        Ok(id) => id,
+        Err(msg) => return Ok(error_result(&msg)),
+    };
+
+    let topic = args.get("topic").and_then(|v| v.as_str()).unwrap_or("");
+    if topic.is_empty() {
+        return Ok(error_result("topic required"));
+    }
+
+    if let Err(e) = deps.db.delete_item(&format!("sub:{}", topic), &user_id).await {
+        tracing::error!("pattern-unsubscribe delete: {}", e);
+        return Ok(error_result("failed to unsubscribe"));
+    }
+
+    Ok(text_result(&serde_json::json!({"unsubscribed": true}).to_string()))
+}
+
+/// `Some(bindings)` if every field `pattern` specifies matches the
+/// corresponding field of `item`, `None` otherwise. `bindings` holds one
+/// entry per [`PatternValue::Capture`] field, keyed by its capture name.
+pub fn match_item(pattern: &Pattern, item: &HashMap<String, Value>) -> Option<HashMap<String, Value>> {
+    let mut bindings = HashMap::new();
+    for (field, expected) in pattern {
+        let actual = item.get(field);
+        match expected {
+            PatternValue::Wildcard => {}
+            PatternValue::Literal { value } => {
+                if actual != Some(value) {
+                    return None;
+                }
+            }
+            PatternValue::Capture { name } => {
+                bindings.insert(name.clone(), actual?.clone());
+            }
+        }
+    }
+    Some(bindings)
+}
+
+/// Evaluate one DynamoDB Streams `NEW_IMAGE` against every pattern stored
+/// under `sub:{topic}`, pushing a notification through web push (falling
+/// back to SMS/email, the same delivery path `channel-notify`'s fallback
+/// uses) to each subscriber whose pattern matches. Returns the number of
+/// subscribers notified.
+pub async fn dispatch_stream_record(deps: &Deps, topic: &str, new_image: &HashMap<String, Value>) -> usize {
+    let candidates = match deps.db.query(&format!("sub:{}", topic)).await {
+        Ok(items) => items,
+        Err(e) => {
+            tracing::error!("pattern dispatch query topic={}: {}", topic, e);
+            return 0;
+        }
+    };
+
+    let mut dispatched = 0;
+    for candidate in &candidates {
+        let subscriber_id = match candidate.get("SK").and_then(|v| v.as_str()) {
+            Some(sk) => sk,
+            None => continue,
+        };
+
+        let pattern: Pattern = match candidate.get("pattern").cloned() {
+            Some(raw) => match serde_json::from_value(raw) {
+                Ok(p) => p,
+                Err(_) => continue,
+            },
+            None => continue,
+        };
+
+        let bindings = match match_item(&pattern, new_image) {
+            Some(b) => b,
+            None => continue,
+        };
+
+        let payload = serde_json::json!({
+            "topic": topic,
+            "item": new_image,
+            "bindings": bindings,
+        });
+
+        if deliver(deps, subscriber_id, &payload).await {
+            dispatched += 1;
+        }
+    }
+    dispatched
+}
+
+/// Deliver `payload` to `subscriber_id` via any web-push subscription it
+/// has registered, falling back to SMS/email at its own identifier when
+/// it has none.
+async fn deliver(deps: &Deps, subscriber_id: &str, payload: &Value) -> bool {
+    let push_subs = deps.db.query(&format!("web-push:{}", subscriber_id)).await.unwrap_or_default();
+
+    let mut sent = false;
+    for ps in &push_subs {
+        if let Some(sub_json) = ps.get("subscription").and_then(|v| v.as_str()) {
+            match webpush::send_web_push(sub_json, payload, &deps.web_push_keys).await {
+                Ok(()) => sent = true,
+                Err(webpush::WebPushError::Gone(status)) => {
+                    tracing::info!(
+                        "web-push subscriber={} gone ({}), pruning subscription",
+                        subscriber_id,
+                        status
+                    );
+                    if let Some(sk) = ps.get("SK").and_then(|v| v.as_str()) {
+                        if let Err(e) = deps.db.delete_item(&format!("web-push:{}", subscriber_id), sk).await {
+                            tracing::error!("web-push prune subscriber={}: {}", subscriber_id, e);
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("web-push send subscriber={}: {}", subscriber_id, e);
+                }
+            }
+        }
+    }
+    if sent {
+        return true;
+    }
+
+    let message = payload.to_string();
+    if subscriber_id.contains('@') {
+        deps.ses
+            .send_email(&deps.ses_from_email, subscriber_id, "Subscription match", &message)
+            .await
+            .map_err(|e| tracing::error!("pattern dispatch fallback email subscriber={}: {}", subscriber_id, e))
+            .is_ok()
+    } else if !subscriber_id.is_empty() {
+        deps.sns
+            .send_sms(subscriber_id, &message)
+            .await
+            .map_err(|e| tracing::error!("pattern dispatch fallback sms subscriber={}: {}", subscriber_id, e))
+            .is_ok()
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_match_item_wildcard_matches_absent_field() {
+        let mut pattern = Pattern::new();
+        pattern.insert("category".into(), PatternValue::Wildcard);
+        let item = HashMap::new();
+
+        assert_eq!(match_item(&pattern, &item), Some(HashMap::new()));
+    }
+
+    #[test]
+    fn test_match_item_literal_mismatch_short_circuits() {
+        let mut pattern = Pattern::new();
+        pattern.insert("category".into(), PatternValue::Literal { value: Value::String("VEHICLES".into()) });
+        let mut item = HashMap::new();
+        item.insert("category".into(), Value::String("ELECTRONICS".into()));
+
+        assert_eq!(match_item(&pattern, &item), None);
+    }
+
+    #[test]
+    fn test_match_item_literal_match() {
+        let mut pattern = Pattern::new();
+        pattern.insert("category".into(), PatternValue::Literal { value: Value::String("VEHICLES".into()) });
+        let mut item = HashMap::new();
+        item.insert("category".into(), Value::String("VEHICLES".into()));
+
+        assert_eq!(match_item(&pattern, &item), Some(HashMap::new()));
+    }
+
+    #[test]
+    fn test_match_item_capture_binds_value() {
+        let mut pattern = Pattern::new();
+        pattern.insert("category".into(), PatternValue::Capture { name: "cat".into() });
+        let mut item = HashMap::new();
+        item.insert("category".into(), Value::String("VEHICLES".into()));
+
+        let bindings = match_item(&pattern, &item).unwrap();
+        assert_eq!(bindings.get("cat"), Some(&Value::String("VEHICLES".into())));
+    }
+
+    #[test]
+    fn test_match_item_capture_requires_presence() {
+        let mut pattern = Pattern::new();
+        pattern.insert("category".into(), PatternValue::Capture { name: "cat".into() });
+        let item = HashMap::new();
+
+        assert_eq!(match_item(&pattern, &item), None);
+    }
+}
@@ -37,10 +37,10 @@ use axum::middleware::{self, Next};
 use axum::response::{IntoResponse, Response};
 use axum::routing::{get, post};
 use axum::{Extension, Json, Router};
-use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use jsonwebtoken::{decode, decode_header, Algorithm, Validation};
 use mcpserver::{
-    text_result, FnToolHandler, JsonRpcRequest, McpError, McpResponse, Server, ToolHandler,
-    ToolResult,
+    text_result, FnToolHandler, JsonRpcRequest, JwksKeyManager, McpError, McpResponse, Server,
+    ToolHandler, ToolResult,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -52,7 +52,7 @@ struct CognitoConfig {
     region: String,
     user_pool_id: String,
     client_id: String,
-    jwks: JwkSet,
+    jwks: Arc<JwksKeyManager>,
 }
 
 impl CognitoConfig {
@@ -68,26 +68,6 @@ impl CognitoConfig {
     }
 }
 
-// ── JWKS types (matches Cognito's JWKS response) ──
-
-#[derive(Debug, Clone, Deserialize)]
-struct JwkSet {
-    keys: Vec<Jwk>,
-}
-
-#[derive(Debug, Clone, Deserialize)]
-struct Jwk {
-    kid: String,
-    n: String,
-    e: String,
-}
-
-impl JwkSet {
-    fn find_key(&self, kid: &str) -> Option<&Jwk> {
-        self.keys.iter().find(|k| k.kid == kid)
-    }
-}
-
 // ── Cognito JWT claims ──
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -131,12 +111,14 @@ async fn require_cognito_jwt(
     let header = decode_header(token).map_err(|_| StatusCode::UNAUTHORIZED)?;
     let kid = header.kid.ok_or(StatusCode::UNAUTHORIZED)?;
 
-    // Find the matching public key from the cached JWKS.
-    let jwk = config.jwks.find_key(&kid).ok_or(StatusCode::UNAUTHORIZED)?;
-
-    // Build the RSA decoding key from the JWK's n and e components.
-    let decoding_key =
-        DecodingKey::from_rsa_components(&jwk.n, &jwk.e).map_err(|_| StatusCode::UNAUTHORIZED)?;
+    // Resolve the decoding key from the JWKS manager — this refreshes
+    // out-of-band on a cache miss, so a rotated key doesn't require a
+    // process restart.
+    let decoding_key = config
+        .jwks
+        .key_for(&kid)
+        .await
+        .ok_or(StatusCode::UNAUTHORIZED)?;
 
     // Validate the token: signature, issuer, expiry.
     let mut validation = Validation::new(Algorithm::RS256);
@@ -235,28 +217,22 @@ async fn main() {
         std::env::var("COGNITO_USER_POOL_ID").expect("COGNITO_USER_POOL_ID must be set");
     let client_id = std::env::var("COGNITO_CLIENT_ID").expect("COGNITO_CLIENT_ID must be set");
 
-    let mut cognito_config = CognitoConfig {
-        region,
-        user_pool_id,
-        client_id,
-        jwks: JwkSet { keys: vec![] },
-    };
+    let issuer = format!("https://cognito-idp.{}.amazonaws.com/{}", region, user_pool_id);
+    let jwks_url = format!("{}/.well-known/jwks.json", issuer);
 
-    // Fetch Cognito's JWKS once at startup and cache it.
-    let jwks_url = cognito_config.jwks_url();
+    // Fetch Cognito's JWKS and spawn the background refresher — keys are
+    // kept current across rotations instead of requiring a restart.
     println!("Fetching JWKS from {}", jwks_url);
-    cognito_config.jwks = reqwest::get(&jwks_url)
-        .await
-        .expect("failed to fetch JWKS")
-        .json::<JwkSet>()
+    let jwks = JwksKeyManager::spawn(jwks_url, std::time::Duration::from_secs(3600))
         .await
-        .expect("failed to parse JWKS");
-    println!(
-        "Loaded {} keys from JWKS",
-        cognito_config.jwks.keys.len()
-    );
+        .expect("failed to fetch JWKS");
 
-    let cognito = Arc::new(cognito_config);
+    let cognito = Arc::new(CognitoConfig {
+        region,
+        user_pool_id,
+        client_id,
+        jwks,
+    });
 
     // Build the MCP server.
     let mut server = Server::builder()
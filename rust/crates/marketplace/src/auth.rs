@@ -1,7 +1,9 @@
-use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use jsonwebtoken::{decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+
 #[derive(Debug, thiserror::Error)]
 pub enum AuthError {
     #[error("invalid or expired token")]
@@ -18,15 +20,109 @@ struct Claims {
     exp: u64,
 }
 
-/// Parse a JWT signed with HMAC-SHA256 and return the userId claim.
-pub fn parse_token(secret: &str, token_str: &str) -> Result<String, AuthError> {
-    let key = DecodingKey::from_secret(secret.as_bytes());
-    let mut validation = Validation::new(Algorithm::HS256);
+/// One verification/signing key in a [`KeySet`]: the `kid` stamped into
+/// the JWT header, the algorithm it's valid for, and the key material
+/// itself. Build one with [`SigningKey::hs256`], [`SigningKey::rs256`], or
+/// [`SigningKey::es256`].
+#[derive(Clone)]
+pub struct SigningKey {
+    kid: String,
+    algorithm: Algorithm,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+}
+
+impl SigningKey {
+    pub fn hs256(kid: impl Into<String>, secret: &str) -> Self {
+        SigningKey {
+            kid: kid.into(),
+            algorithm: Algorithm::HS256,
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+        }
+    }
+
+    /// An RS256 key from PEM-encoded PKCS#1/PKCS#8 material, as issued by
+    /// an external identity service rotating its signing keys.
+    pub fn rs256(kid: impl Into<String>, private_key_pem: &[u8], public_key_pem: &[u8]) -> Result<Self, AuthError> {
+        Ok(SigningKey {
+            kid: kid.into(),
+            algorithm: Algorithm::RS256,
+            encoding_key: EncodingKey::from_rsa_pem(private_key_pem).map_err(|_| AuthError::InvalidToken)?,
+            decoding_key: DecodingKey::from_rsa_pem(public_key_pem).map_err(|_| AuthError::InvalidToken)?,
+        })
+    }
+
+    /// An ES256 (ECDSA P-256) key from PEM-encoded PKCS#8 material.
+    pub fn es256(kid: impl Into<String>, private_key_pem: &[u8], public_key_pem: &[u8]) -> Result<Self, AuthError> {
+        Ok(SigningKey {
+            kid: kid.into(),
+            algorithm: Algorithm::ES256,
+            encoding_key: EncodingKey::from_ec_pem(private_key_pem).map_err(|_| AuthError::InvalidToken)?,
+            decoding_key: DecodingKey::from_ec_pem(public_key_pem).map_err(|_| AuthError::InvalidToken)?,
+        })
+    }
+}
+
+/// A rotatable set of JWT signing/verification keys, keyed by `kid`.
+/// [`create_token`] always signs with the `active` key; [`parse_token`]
+/// will verify against any key in the set, so tokens issued under an
+/// outgoing key keep working (until they naturally expire) while rotation
+/// is in progress — no flag-day where every live token is invalidated at
+/// once.
+pub struct KeySet {
+    keys: HashMap<String, SigningKey>,
+    active_kid: String,
+}
+
+impl KeySet {
+    /// Build a key set from `keys`, signing new tokens with whichever one
+    /// has kid `active_kid`. Panics if `active_kid` isn't present in
+    /// `keys` — a misconfigured deploy should fail at startup rather than
+    /// silently issue tokens nobody can verify.
+    pub fn new(keys: Vec<SigningKey>, active_kid: impl Into<String>) -> Self {
+        let active_kid = active_kid.into();
+        let keys: HashMap<String, SigningKey> = keys.into_iter().map(|k| (k.kid.clone(), k)).collect();
+        assert!(
+            keys.contains_key(&active_kid),
+            "active_kid {} not present in key set",
+            active_kid
+        );
+        KeySet { keys, active_kid }
+    }
+
+    /// A single-key HS256 set — the common case before rotation is set up.
+    pub fn single_hs256(kid: impl Into<String>, secret: &str) -> Self {
+        let kid = kid.into();
+        KeySet::new(vec![SigningKey::hs256(kid.clone(), secret)], kid)
+    }
+
+    fn active(&self) -> &SigningKey {
+        self.keys
+            .get(&self.active_kid)
+            .expect("active_kid always present, enforced in KeySet::new")
+    }
+}
+
+/// Parse a JWT verified against `keys`, selecting the verification key by
+/// the header's `kid` and rejecting anything whose header algorithm
+/// doesn't match that key's own algorithm — so an RS/ES public key can't
+/// be replayed as an HS secret (algorithm confusion), and `alg: none` is
+/// never accepted since no key is ever registered under it.
+pub fn parse_token(keys: &KeySet, token_str: &str) -> Result<String, AuthError> {
+    let header = decode_header(token_str).map_err(|_| AuthError::InvalidToken)?;
+    let kid = header.kid.as_deref().ok_or(AuthError::InvalidToken)?;
+    let key = keys.keys.get(kid).ok_or(AuthError::InvalidToken)?;
+
+    if header.alg != key.algorithm {
+        return Err(AuthError::InvalidToken);
+    }
+
+    let mut validation = Validation::new(key.algorithm);
     validation.required_spec_claims.clear();
     validation.set_required_spec_claims(&["exp"]);
 
-    let data = decode::<Claims>(token_str, &key, &validation)
-        .map_err(|_| AuthError::InvalidToken)?;
+    let data = decode::<Claims>(token_str, &key.decoding_key, &validation).map_err(|_| AuthError::InvalidToken)?;
 
     if data.claims.user_id.is_empty() {
         return Err(AuthError::MissingClaim);
@@ -35,12 +131,12 @@ pub fn parse_token(secret: &str, token_str: &str) -> Result<String, AuthError> {
     Ok(data.claims.user_id)
 }
 
-/// Create a JWT with the given userId claim, signed with HMAC-SHA256.
-pub fn create_token(secret: &str, user_id: &str, expiry_secs: u64) -> Result<String, AuthError> {
-    let now = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap()
-        .as_secs();
+/// Create a JWT for `user_id`, signed with `keys`'s active key and
+/// stamping that key's `kid` into the header so a future verifier (on
+/// this server or an external one sharing the key set) knows which key to
+/// check it against.
+pub fn create_token(keys: &KeySet, user_id: &str, expiry_secs: u64) -> Result<String, AuthError> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
 
     let claims = Claims {
         user_id: user_id.to_string(),
@@ -48,9 +144,11 @@ pub fn create_token(secret: &str, user_id: &str, expiry_secs: u64) -> Result<Str
         exp: now + expiry_secs,
     };
 
-    let key = EncodingKey::from_secret(secret.as_bytes());
-    encode(&Header::new(Algorithm::HS256), &claims, &key)
-        .map_err(|_| AuthError::InvalidToken)
+    let active = keys.active();
+    let mut header = Header::new(active.algorithm);
+    header.kid = Some(active.kid.clone());
+
+    encode(&header, &claims, &active.encoding_key).map_err(|_| AuthError::InvalidToken)
 }
 
 #[cfg(test)]
@@ -59,17 +157,24 @@ mod tests {
 
     const TEST_SECRET: &str = "test-secret-key-for-hmac256";
 
+    fn test_keys() -> KeySet {
+        KeySet::single_hs256("k1", TEST_SECRET)
+    }
+
     #[test]
     fn test_create_and_parse_token() {
-        let token = create_token(TEST_SECRET, "user-123", 3600).unwrap();
-        let user_id = parse_token(TEST_SECRET, &token).unwrap();
+        let keys = test_keys();
+        let token = create_token(&keys, "user-123", 3600).unwrap();
+        let user_id = parse_token(&keys, &token).unwrap();
         assert_eq!(user_id, "user-123");
     }
 
     #[test]
     fn test_parse_wrong_secret() {
-        let token = create_token(TEST_SECRET, "user-123", 3600).unwrap();
-        let result = parse_token("wrong-secret", &token);
+        let keys = test_keys();
+        let token = create_token(&keys, "user-123", 3600).unwrap();
+        let wrong_keys = KeySet::single_hs256("k1", "wrong-secret");
+        let result = parse_token(&wrong_keys, &token);
         assert!(result.is_err());
     }
 
@@ -88,22 +193,72 @@ mod tests {
             exp: past,
         };
 
-        let key = EncodingKey::from_secret(TEST_SECRET.as_bytes());
-        let token = encode(&Header::new(Algorithm::HS256), &claims, &key).unwrap();
-        let result = parse_token(TEST_SECRET, &token);
+        let keys = test_keys();
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("k1".into());
+        let token = encode(&header, &claims, &keys.active().encoding_key).unwrap();
+        let result = parse_token(&keys, &token);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_parse_invalid_string() {
-        let result = parse_token(TEST_SECRET, "not-a-valid-jwt");
+        let keys = test_keys();
+        let result = parse_token(&keys, "not-a-valid-jwt");
         assert!(result.is_err());
     }
 
     #[test]
     fn test_create_and_parse_roundtrip() {
-        let token = create_token(TEST_SECRET, "user-456", 3600).unwrap();
-        let user_id = parse_token(TEST_SECRET, &token).unwrap();
+        let keys = test_keys();
+        let token = create_token(&keys, "user-456", 3600).unwrap();
+        let user_id = parse_token(&keys, &token).unwrap();
         assert_eq!(user_id, "user-456");
     }
+
+    #[test]
+    fn test_parse_rejects_unknown_kid() {
+        let keys = test_keys();
+        let token = create_token(&keys, "user-123", 3600).unwrap();
+
+        let other_keys = KeySet::single_hs256("k2", TEST_SECRET);
+        let result = parse_token(&other_keys, &token);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rotation_verifies_outgoing_and_incoming_keys() {
+        let outgoing = SigningKey::hs256("k1", "outgoing-secret");
+        let incoming = SigningKey::hs256("k2", "incoming-secret");
+
+        // Mid-rotation: both keys verify, but only the incoming one signs.
+        let rotating = KeySet::new(vec![outgoing.clone(), incoming.clone()], "k2");
+
+        // A token issued before rotation, under the now-outgoing key.
+        let old_keys = KeySet::new(vec![outgoing], "k1");
+        let old_token = create_token(&old_keys, "user-123", 3600).unwrap();
+        assert_eq!(parse_token(&rotating, &old_token).unwrap(), "user-123");
+
+        // A token issued mid-rotation, under the active (incoming) key.
+        let new_token = create_token(&rotating, "user-456", 3600).unwrap();
+        assert_eq!(parse_token(&rotating, &new_token).unwrap(), "user-456");
+    }
+
+    #[test]
+    fn test_rejects_algorithm_confusion() {
+        // A header whose `alg` differs from the algorithm its kid is
+        // registered under (here HS384 against an HS256 key) must never
+        // verify, even though the signature checks out against that key's
+        // own secret.
+        let keys = KeySet::single_hs256("k1", TEST_SECRET);
+        let claims = Claims {
+            user_id: "user-123".into(),
+            iat: 0,
+            exp: SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() + 3600,
+        };
+        let mut header = Header::new(Algorithm::HS384); // wrong alg for kid "k1"
+        header.kid = Some("k1".into());
+        let mismatched = encode(&header, &claims, &keys.active().encoding_key).unwrap();
+        assert!(parse_token(&keys, &mismatched).is_err());
+    }
 }
@@ -0,0 +1,17 @@
+//! Standalone migrator binary: provisions the table/GSI schema that
+//! [`marketplace::migrator::ensure_schema`] describes, then exits. Run
+//! this ahead of the Lambda/HTTP handler during deployment rather than
+//! folding schema creation into server startup — a slow `CreateTable`
+//! shouldn't gate the first request.
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let table_name = std::env::var("TABLE_NAME").unwrap_or_else(|_| "app".into());
+
+    let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+    let client = aws_sdk_dynamodb::Client::new(&config);
+
+    marketplace::migrator::ensure_schema(&client, &table_name).await?;
+    println!("table `{}` schema is up to date", table_name);
+    Ok(())
+}
@@ -11,6 +11,98 @@ pub struct KeyPair {
     pub sk: String,
 }
 
+/// Item to upsert as one arm of [`DynamoApi::transact_write`] — the same
+/// shape [`DynamoApi::put_item`] takes, bundled into a struct so a batch
+/// of puts can be passed as a single slice alongside the deletes they
+/// must commit atomically with.
+#[derive(Debug, Clone, Default)]
+pub struct PutRequest {
+    pub pk: String,
+    pub sk: String,
+    pub gsi1pk: String,
+    pub gsi1sk: String,
+    pub gsi2pk: String,
+    pub gsi2sk: String,
+    pub attrs: HashMap<String, Value>,
+}
+
+/// Initial delay before retrying `BatchWriteItem`'s `UnprocessedItems` —
+/// AWS throttled part of the batch and expects callers to back off
+/// rather than resend immediately. Doubles on each retry up to
+/// [`BATCH_RETRY_MAX_BACKOFF`].
+const BATCH_RETRY_INITIAL_BACKOFF: std::time::Duration = std::time::Duration::from_millis(100);
+const BATCH_RETRY_MAX_BACKOFF: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// One page of a [`DynamoApi::query_page`] result. `last_key` mirrors
+/// DynamoDB's `LastEvaluatedKey`: `Some` means the page was truncated
+/// (by the 1 MB limit or by a caller-supplied `page_size`) and querying
+/// again with it as `exclusive_start_key` resumes where this page left
+/// off; `None` means this was the final page.
+#[derive(Debug, Clone, Default)]
+pub struct Page {
+    pub items: Vec<HashMap<String, Value>>,
+    pub last_key: Option<KeyPair>,
+}
+
+/// Full key needed to resume a [`DynamoApi::query_gsi_with_sk_page`] —
+/// unlike [`Page::last_key`]'s plain [`KeyPair`], a GSI's
+/// `ExclusiveStartKey` must also carry the base table's own primary key
+/// alongside the index's, so `(pk, sk)` alone isn't enough to resume.
+#[derive(Debug, Clone)]
+pub struct GsiKeyPair {
+    pub pk: String,
+    pub sk: String,
+    pub gsi_pk: String,
+    pub gsi_sk: String,
+}
+
+/// [`Page`], for [`DynamoApi::query_gsi_with_sk_page`].
+#[derive(Debug, Clone, Default)]
+pub struct GsiPage {
+    pub items: Vec<HashMap<String, Value>>,
+    pub last_key: Option<GsiKeyPair>,
+}
+
+/// Scan direction for [`DynamoApi::query_range`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanDirection {
+    /// Ascending by sort key (`SK > sk_bound` when a bound is given).
+    Forward,
+    /// Descending by sort key (`SK < sk_bound` when a bound is given).
+    Backward,
+}
+
+/// Sort-key comparison for [`DynamoApi::query_sk_compare`] /
+/// [`DynamoApi::query_gsi_sk_compare`] — one method parameterized by
+/// operator rather than four near-identical `<`/`<=`/`>`/`>=` methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkComparison {
+    LessThan,
+    LessThanOrEqual,
+    GreaterThan,
+    GreaterThanOrEqual,
+}
+
+impl SkComparison {
+    fn operator(&self) -> &'static str {
+        match self {
+            SkComparison::LessThan => "<",
+            SkComparison::LessThanOrEqual => "<=",
+            SkComparison::GreaterThan => ">",
+            SkComparison::GreaterThanOrEqual => ">=",
+        }
+    }
+
+    fn matches(&self, lhs: &str, rhs: &str) -> bool {
+        match self {
+            SkComparison::LessThan => lhs < rhs,
+            SkComparison::LessThanOrEqual => lhs <= rhs,
+            SkComparison::GreaterThan => lhs > rhs,
+            SkComparison::GreaterThanOrEqual => lhs >= rhs,
+        }
+    }
+}
+
 /// Trait abstracting DynamoDB operations for testing.
 #[async_trait]
 pub trait DynamoApi: Send + Sync {
@@ -25,6 +117,28 @@ pub trait DynamoApi: Send + Sync {
         attrs: HashMap<String, Value>,
     ) -> Result<(), DynamoError>;
 
+    /// Like [`DynamoApi::put_item`], but conditioned on `expected_version`
+    /// matching what's actually stored right now: `None` requires that no
+    /// item exists yet at `pk`/`sk` (`attribute_not_exists(PK)`); `Some`
+    /// requires the stored `version` attribute to equal it exactly.
+    /// Enforced atomically by DynamoDB's `ConditionExpression`, unlike a
+    /// `get_item` pre-check followed by a plain `put_item` — two
+    /// overlapping writers who both read the same version can't both
+    /// succeed. Fails with [`DynamoError::ConditionalCheckFailed`] when the
+    /// condition doesn't hold, so callers can tell a lost race from any
+    /// other write failure and surface it rather than silently clobbering.
+    async fn put_item_if_version(
+        &self,
+        pk: &str,
+        sk: &str,
+        gsi1pk: &str,
+        gsi1sk: &str,
+        gsi2pk: &str,
+        gsi2sk: &str,
+        attrs: HashMap<String, Value>,
+        expected_version: Option<&HashMap<String, u64>>,
+    ) -> Result<(), DynamoError>;
+
     async fn get_item(&self, pk: &str, sk: &str) -> Result<Option<HashMap<String, Value>>, DynamoError>;
 
     async fn query(&self, pk: &str) -> Result<Vec<HashMap<String, Value>>, DynamoError>;
@@ -43,6 +157,155 @@ pub trait DynamoApi: Send + Sync {
     async fn delete_item(&self, pk: &str, sk: &str) -> Result<(), DynamoError>;
 
     async fn batch_delete_items(&self, items: &[KeyPair]) -> Result<(), DynamoError>;
+
+    /// Write `puts` via `BatchWriteItem`, chunked at 25 items per call
+    /// (DynamoDB's per-request limit) and retried on `UnprocessedItems`
+    /// the same way [`DynamoApi::batch_delete_items`] is. Unlike
+    /// [`DynamoApi::transact_write`], a failure partway through a batch
+    /// can leave some items written and others not — use `transact_write`
+    /// instead when that's not acceptable.
+    async fn batch_put_items(&self, puts: &[PutRequest]) -> Result<(), DynamoError>;
+
+    /// Fetch `keys` via `BatchGetItem`, chunked at 100 keys per call.
+    /// Keys with no matching item are simply absent from the result, same
+    /// as [`DynamoApi::get_item`] returning `None`.
+    async fn batch_get_items(&self, keys: &[KeyPair]) -> Result<Vec<HashMap<String, Value>>, DynamoError>;
+
+    /// Commit `puts` and `deletes` atomically via `TransactWriteItems` —
+    /// all of them land or none do. Use this instead of separate
+    /// `put_item`/`delete_item` calls whenever two items must never be
+    /// observed in a half-written state, e.g. creating a channel record
+    /// alongside its GSI-indexed membership row, or rotating an OTP while
+    /// deleting the old one.
+    async fn transact_write(&self, puts: &[PutRequest], deletes: &[KeyPair]) -> Result<(), DynamoError>;
+
+    /// Atomically add `delta` (negative to decrement) to a numeric
+    /// counter attribute via `UpdateItem`'s `ADD`, creating the item at
+    /// `delta` if it doesn't exist yet. Returns the counter's new value —
+    /// for a maintained index like a per-category channel count, where a
+    /// read-modify-write via `get_item`/`put_item` would race under
+    /// concurrent writers.
+    async fn increment_counter(&self, pk: &str, sk: &str, attr: &str, delta: i64) -> Result<i64, DynamoError>;
+
+    /// Page through a partition's items ordered by sort key, for
+    /// cursor-based history queries (e.g. chat-style `before`/`after`).
+    ///
+    /// `sk_bound`, when present, makes the query exclusive of that key:
+    /// [`ScanDirection::Backward`] returns items with `SK < sk_bound`,
+    /// [`ScanDirection::Forward`] returns items with `SK > sk_bound`. With
+    /// no bound, `Backward` returns the newest `limit` items and `Forward`
+    /// the oldest. Results come back in the scan's native order
+    /// (`Backward` = descending, `Forward` = ascending) — callers that want
+    /// a stable ascending page should sort/reverse as needed.
+    async fn query_range(
+        &self,
+        pk: &str,
+        sk_bound: Option<&str>,
+        direction: ScanDirection,
+        limit: usize,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError>;
+
+    /// Items under `pk` whose sort key compares to `sk` per `op` —
+    /// `SK < :sk`, `SK >= :sk`, and so on. Results come back ascending by
+    /// sort key, same as an unqualified DynamoDB query.
+    async fn query_sk_compare(
+        &self,
+        pk: &str,
+        op: SkComparison,
+        sk: &str,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError>;
+
+    /// Items under `pk` whose sort key starts with `prefix`
+    /// (`begins_with(SK, :p)`) — e.g. every `otp:` entry under a
+    /// hierarchical sort key without pulling the whole partition.
+    async fn query_sk_begins_with(
+        &self,
+        pk: &str,
+        prefix: &str,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError>;
+
+    /// Items under `pk` whose sort key falls in `[lo, hi]` inclusive
+    /// (`SK BETWEEN :lo AND :hi`) — e.g. all entries issued in a time
+    /// window when the sort key is a timestamp.
+    async fn query_sk_between(
+        &self,
+        pk: &str,
+        lo: &str,
+        hi: &str,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError>;
+
+    /// [`DynamoApi::query_sk_compare`] against a GSI's partition/sort key
+    /// pair instead of the table's own.
+    async fn query_gsi_sk_compare(
+        &self,
+        index_name: &str,
+        gsi_pk: &str,
+        op: SkComparison,
+        gsi_sk: &str,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError>;
+
+    /// [`DynamoApi::query_sk_begins_with`] against a GSI.
+    async fn query_gsi_sk_begins_with(
+        &self,
+        index_name: &str,
+        gsi_pk: &str,
+        prefix: &str,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError>;
+
+    /// [`DynamoApi::query_sk_between`] against a GSI.
+    async fn query_gsi_sk_between(
+        &self,
+        index_name: &str,
+        gsi_pk: &str,
+        lo: &str,
+        hi: &str,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError>;
+
+    /// Page through `pk`'s partition, honoring DynamoDB's
+    /// `ExclusiveStartKey`/`LastEvaluatedKey` continuation protocol: pass
+    /// the previous call's [`Page::last_key`] back in to resume.
+    /// `page_size`, when given, is sent as `Limit`; `None` lets DynamoDB
+    /// page at its own 1 MB boundary. Unlike [`DynamoApi::query`], a
+    /// truncated result is reported via `last_key` rather than silently
+    /// dropped — essential for a partition too large for one page (e.g. a
+    /// user with thousands of stored subscriptions).
+    async fn query_page(
+        &self,
+        pk: &str,
+        exclusive_start_key: Option<KeyPair>,
+        page_size: Option<i32>,
+    ) -> Result<Page, DynamoError>;
+
+    /// [`DynamoApi::query_page`] against a GSI's equality match (`gsi_pk`,
+    /// `gsi_sk`) instead of the table's own partition — e.g. paging
+    /// through every channel in one category. Takes/returns
+    /// [`GsiKeyPair`] rather than [`KeyPair`] to resume, since a GSI's
+    /// `ExclusiveStartKey` must carry the base table's primary key too.
+    async fn query_gsi_with_sk_page(
+        &self,
+        index_name: &str,
+        gsi_pk: &str,
+        gsi_sk: &str,
+        exclusive_start_key: Option<GsiKeyPair>,
+        page_size: Option<i32>,
+    ) -> Result<GsiPage, DynamoError>;
+
+    /// Collect every page of [`DynamoApi::query_page`] into a single
+    /// `Vec`, for callers that want the whole partition and are fine
+    /// paying for however many round trips that costs.
+    async fn query_all(&self, pk: &str) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+        let mut items = Vec::new();
+        let mut exclusive_start_key = None;
+        loop {
+            let page = self.query_page(pk, exclusive_start_key, None).await?;
+            items.extend(page.items);
+            exclusive_start_key = page.last_key;
+            if exclusive_start_key.is_none() {
+                break;
+            }
+        }
+        Ok(items)
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -51,6 +314,8 @@ pub enum DynamoError {
     Sdk(String),
     #[error("marshal error: {0}")]
     Marshal(String),
+    #[error("conditional check failed")]
+    ConditionalCheckFailed,
 }
 
 /// Real DynamoDB client implementation.
@@ -68,6 +333,23 @@ impl DynamoClient {
             table_name: table_name.to_string(),
         })
     }
+
+    /// Like [`DynamoClient::new`], but points the SDK at `endpoint_url`
+    /// instead of resolving AWS's regional endpoint — for running the
+    /// real query/GSI/batch/transact paths against a containerized
+    /// DynamoDB-Local in integration tests, rather than only against
+    /// `mock::MockDynamo`.
+    pub async fn with_endpoint(table_name: &str, endpoint_url: &str) -> Result<Self, DynamoError> {
+        let config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+        let dynamo_config = aws_sdk_dynamodb::config::Builder::from(&config)
+            .endpoint_url(endpoint_url)
+            .build();
+        let client = DynamoDbClient::from_conf(dynamo_config);
+        Ok(Self {
+            client,
+            table_name: table_name.to_string(),
+        })
+    }
 }
 
 fn json_to_av(val: &Value) -> AttributeValue {
@@ -175,6 +457,61 @@ impl DynamoApi for DynamoClient {
         Ok(())
     }
 
+    async fn put_item_if_version(
+        &self,
+        pk: &str,
+        sk: &str,
+        gsi1pk: &str,
+        gsi1sk: &str,
+        gsi2pk: &str,
+        gsi2sk: &str,
+        attrs: HashMap<String, Value>,
+        expected_version: Option<&HashMap<String, u64>>,
+    ) -> Result<(), DynamoError> {
+        let mut item: HashMap<String, AttributeValue> = HashMap::new();
+        item.insert("PK".into(), AttributeValue::S(pk.into()));
+        item.insert("SK".into(), AttributeValue::S(sk.into()));
+
+        if !gsi1pk.is_empty() {
+            item.insert("GSI1PK".into(), AttributeValue::S(gsi1pk.into()));
+        }
+        if !gsi1sk.is_empty() {
+            item.insert("GSI1SK".into(), AttributeValue::S(gsi1sk.into()));
+        }
+        if !gsi2pk.is_empty() {
+            item.insert("GSI2PK".into(), AttributeValue::S(gsi2pk.into()));
+        }
+        if !gsi2sk.is_empty() {
+            item.insert("GSI2SK".into(), AttributeValue::S(gsi2sk.into()));
+        }
+
+        for (k, v) in &attrs {
+            item.insert(k.clone(), json_to_av(v));
+        }
+
+        let mut request = self.client.put_item().table_name(&self.table_name).set_item(Some(item));
+        request = match expected_version {
+            None => request.condition_expression("attribute_not_exists(PK)"),
+            Some(expected) => request
+                .condition_expression("version = :expected_version")
+                .expression_attribute_values(
+                    ":expected_version",
+                    json_to_av(&serde_json::to_value(expected).unwrap_or(Value::Null)),
+                ),
+        };
+
+        match request.send().await {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                if e.as_service_error().is_some_and(|se| se.is_conditional_check_failed_exception()) {
+                    Err(DynamoError::ConditionalCheckFailed)
+                } else {
+                    Err(DynamoError::Sdk(e.to_string()))
+                }
+            }
+        }
+    }
+
     async fn get_item(&self, pk: &str, sk: &str) -> Result<Option<HashMap<String, Value>>, DynamoError> {
         let out = self
             .client
@@ -285,7 +622,7 @@ impl DynamoApi for DynamoClient {
         }
 
         for chunk in items.chunks(25) {
-            let requests: Vec<aws_sdk_dynamodb::types::WriteRequest> = chunk
+            let mut requests: Vec<aws_sdk_dynamodb::types::WriteRequest> = chunk
                 .iter()
                 .map(|kp| {
                     aws_sdk_dynamodb::types::WriteRequest::builder()
@@ -300,124 +637,654 @@ impl DynamoApi for DynamoClient {
                 })
                 .collect();
 
-            self.client
-                .batch_write_item()
-                .request_items(&self.table_name, requests)
-                .send()
-                .await
-                .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+            let mut backoff = BATCH_RETRY_INITIAL_BACKOFF;
+            loop {
+                let out = self
+                    .client
+                    .batch_write_item()
+                    .request_items(&self.table_name, requests)
+                    .send()
+                    .await
+                    .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+                let unprocessed = out
+                    .unprocessed_items
+                    .and_then(|mut items| items.remove(&self.table_name))
+                    .unwrap_or_default();
+                if unprocessed.is_empty() {
+                    break;
+                }
+
+                // A chunk's writes throttled partway through — the SDK
+                // reports exactly which ones didn't land in
+                // `UnprocessedItems`, so retry only those rather than the
+                // whole chunk.
+                requests = unprocessed;
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(BATCH_RETRY_MAX_BACKOFF);
+            }
         }
 
         Ok(())
     }
-}
 
-// ── In-memory mock for testing ──
+    async fn batch_put_items(&self, puts: &[PutRequest]) -> Result<(), DynamoError> {
+        if puts.is_empty() {
+            return Ok(());
+        }
 
-#[cfg(test)]
-pub mod mock {
-    use super::*;
-    use std::sync::Mutex;
+        for chunk in puts.chunks(25) {
+            let mut requests: Vec<aws_sdk_dynamodb::types::WriteRequest> = chunk
+                .iter()
+                .map(|put| {
+                    let mut item: HashMap<String, AttributeValue> = HashMap::new();
+                    item.insert("PK".into(), AttributeValue::S(put.pk.clone()));
+                    item.insert("SK".into(), AttributeValue::S(put.sk.clone()));
+                    if !put.gsi1pk.is_empty() {
+                        item.insert("GSI1PK".into(), AttributeValue::S(put.gsi1pk.clone()));
+                    }
+                    if !put.gsi1sk.is_empty() {
+                        item.insert("GSI1SK".into(), AttributeValue::S(put.gsi1sk.clone()));
+                    }
+                    if !put.gsi2pk.is_empty() {
+                        item.insert("GSI2PK".into(), AttributeValue::S(put.gsi2pk.clone()));
+                    }
+                    if !put.gsi2sk.is_empty() {
+                        item.insert("GSI2SK".into(), AttributeValue::S(put.gsi2sk.clone()));
+                    }
+                    for (k, v) in &put.attrs {
+                        item.insert(k.clone(), json_to_av(v));
+                    }
 
-    /// In-memory DynamoDB mock for testing.
-    pub struct MockDynamo {
-        items: Mutex<HashMap<String, HashMap<String, AttributeValue>>>,
-    }
+                    aws_sdk_dynamodb::types::WriteRequest::builder()
+                        .put_request(
+                            aws_sdk_dynamodb::types::PutRequest::builder()
+                                .set_item(Some(item))
+                                .build()
+                                .unwrap(),
+                        )
+                        .build()
+                })
+                .collect();
 
-    impl MockDynamo {
-        pub fn new() -> Self {
-            Self {
-                items: Mutex::new(HashMap::new()),
+            let mut backoff = BATCH_RETRY_INITIAL_BACKOFF;
+            loop {
+                let out = self
+                    .client
+                    .batch_write_item()
+                    .request_items(&self.table_name, requests)
+                    .send()
+                    .await
+                    .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+                let unprocessed = out
+                    .unprocessed_items
+                    .and_then(|mut items| items.remove(&self.table_name))
+                    .unwrap_or_default();
+                if unprocessed.is_empty() {
+                    break;
+                }
+
+                requests = unprocessed;
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(BATCH_RETRY_MAX_BACKOFF);
             }
         }
 
-        fn make_key(pk: &str, sk: &str) -> String {
-            format!("{}|{}", pk, sk)
+        Ok(())
+    }
+
+    async fn batch_get_items(&self, keys: &[KeyPair]) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut results = Vec::new();
+
+        for chunk in keys.chunks(100) {
+            let mut keys_and_attrs = aws_sdk_dynamodb::types::KeysAndAttributes::builder();
+            for kp in chunk {
+                keys_and_attrs = keys_and_attrs.keys(HashMap::from([
+                    ("PK".to_string(), AttributeValue::S(kp.pk.clone())),
+                    ("SK".to_string(), AttributeValue::S(kp.sk.clone())),
+                ]));
+            }
+            let keys_and_attrs = keys_and_attrs
+                .build()
+                .map_err(|e| DynamoError::Marshal(e.to_string()))?;
+
+            let mut request_items = HashMap::from([(self.table_name.clone(), keys_and_attrs)]);
+            loop {
+                let out = self
+                    .client
+                    .batch_get_item()
+                    .set_request_items(Some(request_items))
+                    .send()
+                    .await
+                    .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+                if let Some(mut responses) = out.responses {
+                    if let Some(items) = responses.remove(&self.table_name) {
+                        results.extend(items_to_json(&items));
+                    }
+                }
+
+                let unprocessed = out.unprocessed_keys.unwrap_or_default();
+                if unprocessed.is_empty() {
+                    break;
+                }
+                request_items = unprocessed;
+            }
         }
+
+        Ok(results)
     }
 
-    #[async_trait]
-    impl DynamoApi for MockDynamo {
-        async fn put_item(
-            &self,
-            pk: &str,
-            sk: &str,
-            gsi1pk: &str,
-            gsi1sk: &str,
-            gsi2pk: &str,
-            gsi2sk: &str,
-            attrs: HashMap<String, Value>,
-        ) -> Result<(), DynamoError> {
+    async fn transact_write(&self, puts: &[PutRequest], deletes: &[KeyPair]) -> Result<(), DynamoError> {
+        if puts.is_empty() && deletes.is_empty() {
+            return Ok(());
+        }
+
+        let mut transact_items = Vec::with_capacity(puts.len() + deletes.len());
+
+        for put in puts {
             let mut item: HashMap<String, AttributeValue> = HashMap::new();
-            item.insert("PK".into(), AttributeValue::S(pk.into()));
-            item.insert("SK".into(), AttributeValue::S(sk.into()));
-            if !gsi1pk.is_empty() {
-                item.insert("GSI1PK".into(), AttributeValue::S(gsi1pk.into()));
+            item.insert("PK".into(), AttributeValue::S(put.pk.clone()));
+            item.insert("SK".into(), AttributeValue::S(put.sk.clone()));
+            if !put.gsi1pk.is_empty() {
+                item.insert("GSI1PK".into(), AttributeValue::S(put.gsi1pk.clone()));
             }
-            if !gsi1sk.is_empty() {
-                item.insert("GSI1SK".into(), AttributeValue::S(gsi1sk.into()));
+            if !put.gsi1sk.is_empty() {
+                item.insert("GSI1SK".into(), AttributeValue::S(put.gsi1sk.clone()));
             }
-            if !gsi2pk.is_empty() {
-                item.insert("GSI2PK".into(), AttributeValue::S(gsi2pk.into()));
+            if !put.gsi2pk.is_empty() {
+                item.insert("GSI2PK".into(), AttributeValue::S(put.gsi2pk.clone()));
             }
-            if !gsi2sk.is_empty() {
-                item.insert("GSI2SK".into(), AttributeValue::S(gsi2sk.into()));
+            if !put.gsi2sk.is_empty() {
+                item.insert("GSI2SK".into(), AttributeValue::S(put.gsi2sk.clone()));
             }
-            for (k, v) in &attrs {
+            for (k, v) in &put.attrs {
                 item.insert(k.clone(), json_to_av(v));
             }
-            let key = Self::make_key(pk, sk);
-            self.items.lock().unwrap().insert(key, item);
-            Ok(())
-        }
 
-        async fn get_item(&self, pk: &str, sk: &str) -> Result<Option<HashMap<String, Value>>, DynamoError> {
-            let key = Self::make_key(pk, sk);
-            let items = self.items.lock().unwrap();
-            Ok(items.get(&key).map(|item| {
-                item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect()
-            }))
+            let put_req = aws_sdk_dynamodb::types::Put::builder()
+                .table_name(&self.table_name)
+                .set_item(Some(item))
+                .build()
+                .map_err(|e| DynamoError::Marshal(e.to_string()))?;
+            transact_items.push(
+                aws_sdk_dynamodb::types::TransactWriteItem::builder()
+                    .put(put_req)
+                    .build(),
+            );
         }
 
-        async fn query(&self, pk: &str) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
-            let items = self.items.lock().unwrap();
-            let results: Vec<_> = items
-                .values()
-                .filter(|item| {
-                    item.get("PK")
-                        .and_then(|v| if let AttributeValue::S(s) = v { Some(s.as_str()) } else { None })
-                        == Some(pk)
-                })
-                .map(|item| item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect())
-                .collect();
-            Ok(results)
+        for kp in deletes {
+            let delete_req = aws_sdk_dynamodb::types::Delete::builder()
+                .table_name(&self.table_name)
+                .key("PK", AttributeValue::S(kp.pk.clone()))
+                .key("SK", AttributeValue::S(kp.sk.clone()))
+                .build()
+                .map_err(|e| DynamoError::Marshal(e.to_string()))?;
+            transact_items.push(
+                aws_sdk_dynamodb::types::TransactWriteItem::builder()
+                    .delete(delete_req)
+                    .build(),
+            );
         }
 
-        async fn query_with_sk(&self, pk: &str, sk: &str) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
-            let key = Self::make_key(pk, sk);
-            let items = self.items.lock().unwrap();
-            Ok(items
-                .get(&key)
-                .map(|item| vec![item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect()])
-                .unwrap_or_default())
-        }
+        self.client
+            .transact_write_items()
+            .set_transact_items(Some(transact_items))
+            .send()
+            .await
+            .map_err(|e| DynamoError::Sdk(e.to_string()))?;
 
-        async fn query_gsi(&self, index_name: &str, gsi_pk: &str) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
-            let pk_attr = if index_name == "GSI2" { "GSI2PK" } else { "GSI1PK" };
-            let items = self.items.lock().unwrap();
-            let results: Vec<_> = items
-                .values()
-                .filter(|item| {
-                    item.get(pk_attr)
-                        .and_then(|v| if let AttributeValue::S(s) = v { Some(s.as_str()) } else { None })
-                        == Some(gsi_pk)
-                })
-                .map(|item| item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect())
-                .collect();
-            Ok(results)
+        Ok(())
+    }
+
+    async fn increment_counter(&self, pk: &str, sk: &str, attr: &str, delta: i64) -> Result<i64, DynamoError> {
+        let out = self
+            .client
+            .update_item()
+            .table_name(&self.table_name)
+            .key("PK", AttributeValue::S(pk.into()))
+            .key("SK", AttributeValue::S(sk.into()))
+            .update_expression("ADD #attr :delta")
+            .expression_attribute_names("#attr", attr)
+            .expression_attribute_values(":delta", AttributeValue::N(delta.to_string()))
+            .return_values(aws_sdk_dynamodb::types::ReturnValue::UpdatedNew)
+            .send()
+            .await
+            .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+        Ok(out
+            .attributes()
+            .and_then(|a| a.get(attr))
+            .and_then(|v| v.as_n().ok())
+            .and_then(|n| n.parse::<i64>().ok())
+            .unwrap_or(0))
+    }
+
+    async fn query_range(
+        &self,
+        pk: &str,
+        sk_bound: Option<&str>,
+        direction: ScanDirection,
+        limit: usize,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+        let key_cond = match (sk_bound, direction) {
+            (Some(_), ScanDirection::Backward) => "PK = :pk AND SK < :sk",
+            (Some(_), ScanDirection::Forward) => "PK = :pk AND SK > :sk",
+            (None, _) => "PK = :pk",
+        };
+
+        let mut query = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .key_condition_expression(key_cond)
+            .expression_attribute_values(":pk", AttributeValue::S(pk.into()))
+            .scan_index_forward(direction == ScanDirection::Forward)
+            .limit(limit as i32);
+
+        if let Some(sk) = sk_bound {
+            query = query.expression_attribute_values(":sk", AttributeValue::S(sk.into()));
         }
 
-        async fn query_gsi_with_sk(
+        let out = query
+            .send()
+            .await
+            .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+        Ok(items_to_json(out.items()))
+    }
+
+    async fn query_sk_compare(
+        &self,
+        pk: &str,
+        op: SkComparison,
+        sk: &str,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+        let out = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .key_condition_expression(format!("PK = :pk AND SK {} :sk", op.operator()))
+            .expression_attribute_values(":pk", AttributeValue::S(pk.into()))
+            .expression_attribute_values(":sk", AttributeValue::S(sk.into()))
+            .send()
+            .await
+            .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+        Ok(items_to_json(out.items()))
+    }
+
+    async fn query_sk_begins_with(
+        &self,
+        pk: &str,
+        prefix: &str,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+        let out = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .key_condition_expression("PK = :pk AND begins_with(SK, :p)")
+            .expression_attribute_values(":pk", AttributeValue::S(pk.into()))
+            .expression_attribute_values(":p", AttributeValue::S(prefix.into()))
+            .send()
+            .await
+            .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+        Ok(items_to_json(out.items()))
+    }
+
+    async fn query_sk_between(
+        &self,
+        pk: &str,
+        lo: &str,
+        hi: &str,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+        let out = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .key_condition_expression("PK = :pk AND SK BETWEEN :lo AND :hi")
+            .expression_attribute_values(":pk", AttributeValue::S(pk.into()))
+            .expression_attribute_values(":lo", AttributeValue::S(lo.into()))
+            .expression_attribute_values(":hi", AttributeValue::S(hi.into()))
+            .send()
+            .await
+            .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+        Ok(items_to_json(out.items()))
+    }
+
+    async fn query_gsi_sk_compare(
+        &self,
+        index_name: &str,
+        gsi_pk: &str,
+        op: SkComparison,
+        gsi_sk: &str,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+        let (pk_attr, sk_attr) = if index_name == "GSI2" {
+            ("GSI2PK", "GSI2SK")
+        } else {
+            ("GSI1PK", "GSI1SK")
+        };
+
+        let out = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .index_name(index_name)
+            .key_condition_expression(format!("{} = :pk AND {} {} :sk", pk_attr, sk_attr, op.operator()))
+            .expression_attribute_values(":pk", AttributeValue::S(gsi_pk.into()))
+            .expression_attribute_values(":sk", AttributeValue::S(gsi_sk.into()))
+            .send()
+            .await
+            .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+        Ok(items_to_json(out.items()))
+    }
+
+    async fn query_gsi_sk_begins_with(
+        &self,
+        index_name: &str,
+        gsi_pk: &str,
+        prefix: &str,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+        let (pk_attr, sk_attr) = if index_name == "GSI2" {
+            ("GSI2PK", "GSI2SK")
+        } else {
+            ("GSI1PK", "GSI1SK")
+        };
+
+        let out = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .index_name(index_name)
+            .key_condition_expression(format!("{} = :pk AND begins_with({}, :p)", pk_attr, sk_attr))
+            .expression_attribute_values(":pk", AttributeValue::S(gsi_pk.into()))
+            .expression_attribute_values(":p", AttributeValue::S(prefix.into()))
+            .send()
+            .await
+            .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+        Ok(items_to_json(out.items()))
+    }
+
+    async fn query_gsi_sk_between(
+        &self,
+        index_name: &str,
+        gsi_pk: &str,
+        lo: &str,
+        hi: &str,
+    ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+        let (pk_attr, sk_attr) = if index_name == "GSI2" {
+            ("GSI2PK", "GSI2SK")
+        } else {
+            ("GSI1PK", "GSI1SK")
+        };
+
+        let out = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .index_name(index_name)
+            .key_condition_expression(format!("{} = :pk AND {} BETWEEN :lo AND :hi", pk_attr, sk_attr))
+            .expression_attribute_values(":pk", AttributeValue::S(gsi_pk.into()))
+            .expression_attribute_values(":lo", AttributeValue::S(lo.into()))
+            .expression_attribute_values(":hi", AttributeValue::S(hi.into()))
+            .send()
+            .await
+            .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+        Ok(items_to_json(out.items()))
+    }
+
+    async fn query_page(
+        &self,
+        pk: &str,
+        exclusive_start_key: Option<KeyPair>,
+        page_size: Option<i32>,
+    ) -> Result<Page, DynamoError> {
+        let mut query = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .key_condition_expression("PK = :pk")
+            .expression_attribute_values(":pk", AttributeValue::S(pk.into()));
+
+        if let Some(size) = page_size {
+            query = query.limit(size);
+        }
+        if let Some(key) = &exclusive_start_key {
+            query = query
+                .exclusive_start_key("PK", AttributeValue::S(key.pk.clone()))
+                .exclusive_start_key("SK", AttributeValue::S(key.sk.clone()));
+        }
+
+        let out = query.send().await.map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+        let last_key = out.last_evaluated_key().and_then(|key| {
+            let pk = key.get("PK").and_then(|v| if let AttributeValue::S(s) = v { Some(s.clone()) } else { None })?;
+            let sk = key.get("SK").and_then(|v| if let AttributeValue::S(s) = v { Some(s.clone()) } else { None })?;
+            Some(KeyPair { pk, sk })
+        });
+
+        Ok(Page { items: items_to_json(out.items()), last_key })
+    }
+
+    async fn query_gsi_with_sk_page(
+        &self,
+        index_name: &str,
+        gsi_pk: &str,
+        gsi_sk: &str,
+        exclusive_start_key: Option<GsiKeyPair>,
+        page_size: Option<i32>,
+    ) -> Result<GsiPage, DynamoError> {
+        let (pk_attr, sk_attr) = if index_name == "GSI2" {
+            ("GSI2PK", "GSI2SK")
+        } else {
+            ("GSI1PK", "GSI1SK")
+        };
+
+        let mut query = self
+            .client
+            .query()
+            .table_name(&self.table_name)
+            .index_name(index_name)
+            .key_condition_expression(format!("{} = :pk AND {} = :sk", pk_attr, sk_attr))
+            .expression_attribute_values(":pk", AttributeValue::S(gsi_pk.into()))
+            .expression_attribute_values(":sk", AttributeValue::S(gsi_sk.into()));
+
+        if let Some(size) = page_size {
+            query = query.limit(size);
+        }
+        if let Some(key) = &exclusive_start_key {
+            query = query
+                .exclusive_start_key("PK", AttributeValue::S(key.pk.clone()))
+                .exclusive_start_key("SK", AttributeValue::S(key.sk.clone()))
+                .exclusive_start_key(pk_attr, AttributeValue::S(key.gsi_pk.clone()))
+                .exclusive_start_key(sk_attr, AttributeValue::S(key.gsi_sk.clone()));
+        }
+
+        let out = query.send().await.map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+        let last_key = out.last_evaluated_key().and_then(|key| {
+            let pk = key.get("PK").and_then(|v| if let AttributeValue::S(s) = v { Some(s.clone()) } else { None })?;
+            let sk = key.get("SK").and_then(|v| if let AttributeValue::S(s) = v { Some(s.clone()) } else { None })?;
+            let gsi_pk = key.get(pk_attr).and_then(|v| if let AttributeValue::S(s) = v { Some(s.clone()) } else { None })?;
+            let gsi_sk = key.get(sk_attr).and_then(|v| if let AttributeValue::S(s) = v { Some(s.clone()) } else { None })?;
+            Some(GsiKeyPair { pk, sk, gsi_pk, gsi_sk })
+        });
+
+        Ok(GsiPage { items: items_to_json(out.items()), last_key })
+    }
+}
+
+// ── In-memory mock for testing ──
+
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// In-memory DynamoDB mock for testing.
+    pub struct MockDynamo {
+        items: Mutex<HashMap<String, HashMap<String, AttributeValue>>>,
+    }
+
+    impl MockDynamo {
+        pub fn new() -> Self {
+            Self {
+                items: Mutex::new(HashMap::new()),
+            }
+        }
+
+        fn make_key(pk: &str, sk: &str) -> String {
+            format!("{}|{}", pk, sk)
+        }
+    }
+
+    #[async_trait]
+    impl DynamoApi for MockDynamo {
+        async fn put_item(
+            &self,
+            pk: &str,
+            sk: &str,
+            gsi1pk: &str,
+            gsi1sk: &str,
+            gsi2pk: &str,
+            gsi2sk: &str,
+            attrs: HashMap<String, Value>,
+        ) -> Result<(), DynamoError> {
+            let mut item: HashMap<String, AttributeValue> = HashMap::new();
+            item.insert("PK".into(), AttributeValue::S(pk.into()));
+            item.insert("SK".into(), AttributeValue::S(sk.into()));
+            if !gsi1pk.is_empty() {
+                item.insert("GSI1PK".into(), AttributeValue::S(gsi1pk.into()));
+            }
+            if !gsi1sk.is_empty() {
+                item.insert("GSI1SK".into(), AttributeValue::S(gsi1sk.into()));
+            }
+            if !gsi2pk.is_empty() {
+                item.insert("GSI2PK".into(), AttributeValue::S(gsi2pk.into()));
+            }
+            if !gsi2sk.is_empty() {
+                item.insert("GSI2SK".into(), AttributeValue::S(gsi2sk.into()));
+            }
+            for (k, v) in &attrs {
+                item.insert(k.clone(), json_to_av(v));
+            }
+            let key = Self::make_key(pk, sk);
+            self.items.lock().unwrap().insert(key, item);
+            Ok(())
+        }
+
+        async fn put_item_if_version(
+            &self,
+            pk: &str,
+            sk: &str,
+            gsi1pk: &str,
+            gsi1sk: &str,
+            gsi2pk: &str,
+            gsi2sk: &str,
+            attrs: HashMap<String, Value>,
+            expected_version: Option<&HashMap<String, u64>>,
+        ) -> Result<(), DynamoError> {
+            let key = Self::make_key(pk, sk);
+            let mut store = self.items.lock().unwrap();
+
+            // Held across the check-and-write so a racing caller can't
+            // slip a write in between, the same atomicity a real
+            // `ConditionExpression` gives us server-side.
+            let stored_version: Option<HashMap<String, u64>> = store.get(&key).map(|item| {
+                item.get("version")
+                    .map(av_to_json)
+                    .and_then(|v| serde_json::from_value(v).ok())
+                    .unwrap_or_default()
+            });
+
+            let condition_met = match expected_version {
+                None => stored_version.is_none(),
+                Some(expected) => stored_version.as_ref() == Some(expected),
+            };
+            if !condition_met {
+                return Err(DynamoError::ConditionalCheckFailed);
+            }
+
+            let mut item: HashMap<String, AttributeValue> = HashMap::new();
+            item.insert("PK".into(), AttributeValue::S(pk.into()));
+            item.insert("SK".into(), AttributeValue::S(sk.into()));
+            if !gsi1pk.is_empty() {
+                item.insert("GSI1PK".into(), AttributeValue::S(gsi1pk.into()));
+            }
+            if !gsi1sk.is_empty() {
+                item.insert("GSI1SK".into(), AttributeValue::S(gsi1sk.into()));
+            }
+            if !gsi2pk.is_empty() {
+                item.insert("GSI2PK".into(), AttributeValue::S(gsi2pk.into()));
+            }
+            if !gsi2sk.is_empty() {
+                item.insert("GSI2SK".into(), AttributeValue::S(gsi2sk.into()));
+            }
+            for (k, v) in &attrs {
+                item.insert(k.clone(), json_to_av(v));
+            }
+            store.insert(key, item);
+            Ok(())
+        }
+
+        async fn get_item(&self, pk: &str, sk: &str) -> Result<Option<HashMap<String, Value>>, DynamoError> {
+            let key = Self::make_key(pk, sk);
+            let items = self.items.lock().unwrap();
+            Ok(items.get(&key).map(|item| {
+                item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect()
+            }))
+        }
+
+        async fn query(&self, pk: &str) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+            let items = self.items.lock().unwrap();
+            let results: Vec<_> = items
+                .values()
+                .filter(|item| {
+                    item.get("PK")
+                        .and_then(|v| if let AttributeValue::S(s) = v { Some(s.as_str()) } else { None })
+                        == Some(pk)
+                })
+                .map(|item| item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect())
+                .collect();
+            Ok(results)
+        }
+
+        async fn query_with_sk(&self, pk: &str, sk: &str) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+            let key = Self::make_key(pk, sk);
+            let items = self.items.lock().unwrap();
+            Ok(items
+                .get(&key)
+                .map(|item| vec![item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect()])
+                .unwrap_or_default())
+        }
+
+        async fn query_gsi(&self, index_name: &str, gsi_pk: &str) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+            let pk_attr = if index_name == "GSI2" { "GSI2PK" } else { "GSI1PK" };
+            let items = self.items.lock().unwrap();
+            let results: Vec<_> = items
+                .values()
+                .filter(|item| {
+                    item.get(pk_attr)
+                        .and_then(|v| if let AttributeValue::S(s) = v { Some(s.as_str()) } else { None })
+                        == Some(gsi_pk)
+                })
+                .map(|item| item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect())
+                .collect();
+            Ok(results)
+        }
+
+        async fn query_gsi_with_sk(
             &self,
             index_name: &str,
             gsi_pk: &str,
@@ -461,6 +1328,359 @@ pub mod mock {
             }
             Ok(())
         }
+
+        async fn batch_put_items(&self, puts: &[PutRequest]) -> Result<(), DynamoError> {
+            let mut store = self.items.lock().unwrap();
+            for put in puts {
+                let mut item: HashMap<String, AttributeValue> = HashMap::new();
+                item.insert("PK".into(), AttributeValue::S(put.pk.clone()));
+                item.insert("SK".into(), AttributeValue::S(put.sk.clone()));
+                if !put.gsi1pk.is_empty() {
+                    item.insert("GSI1PK".into(), AttributeValue::S(put.gsi1pk.clone()));
+                }
+                if !put.gsi1sk.is_empty() {
+                    item.insert("GSI1SK".into(), AttributeValue::S(put.gsi1sk.clone()));
+                }
+                if !put.gsi2pk.is_empty() {
+                    item.insert("GSI2PK".into(), AttributeValue::S(put.gsi2pk.clone()));
+                }
+                if !put.gsi2sk.is_empty() {
+                    item.insert("GSI2SK".into(), AttributeValue::S(put.gsi2sk.clone()));
+                }
+                for (k, v) in &put.attrs {
+                    item.insert(k.clone(), json_to_av(v));
+                }
+                store.insert(Self::make_key(&put.pk, &put.sk), item);
+            }
+            Ok(())
+        }
+
+        async fn batch_get_items(&self, keys: &[KeyPair]) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+            let store = self.items.lock().unwrap();
+            Ok(keys
+                .iter()
+                .filter_map(|kp| store.get(&Self::make_key(&kp.pk, &kp.sk)))
+                .map(|item| item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect())
+                .collect())
+        }
+
+        async fn transact_write(&self, puts: &[PutRequest], deletes: &[KeyPair]) -> Result<(), DynamoError> {
+            // The mock holds a single lock for the whole call, so this is
+            // atomic with respect to any other `MockDynamo` method by
+            // construction — there's no partial-write window to simulate.
+            let mut store = self.items.lock().unwrap();
+
+            for put in puts {
+                let mut item: HashMap<String, AttributeValue> = HashMap::new();
+                item.insert("PK".into(), AttributeValue::S(put.pk.clone()));
+                item.insert("SK".into(), AttributeValue::S(put.sk.clone()));
+                if !put.gsi1pk.is_empty() {
+                    item.insert("GSI1PK".into(), AttributeValue::S(put.gsi1pk.clone()));
+                }
+                if !put.gsi1sk.is_empty() {
+                    item.insert("GSI1SK".into(), AttributeValue::S(put.gsi1sk.clone()));
+                }
+                if !put.gsi2pk.is_empty() {
+                    item.insert("GSI2PK".into(), AttributeValue::S(put.gsi2pk.clone()));
+                }
+                if !put.gsi2sk.is_empty() {
+                    item.insert("GSI2SK".into(), AttributeValue::S(put.gsi2sk.clone()));
+                }
+                for (k, v) in &put.attrs {
+                    item.insert(k.clone(), json_to_av(v));
+                }
+                store.insert(Self::make_key(&put.pk, &put.sk), item);
+            }
+
+            for kp in deletes {
+                store.remove(&Self::make_key(&kp.pk, &kp.sk));
+            }
+
+            Ok(())
+        }
+
+        async fn increment_counter(&self, pk: &str, sk: &str, attr: &str, delta: i64) -> Result<i64, DynamoError> {
+            let key = Self::make_key(pk, sk);
+            let mut store = self.items.lock().unwrap();
+            let item = store.entry(key).or_insert_with(|| {
+                let mut item = HashMap::new();
+                item.insert("PK".into(), AttributeValue::S(pk.to_string()));
+                item.insert("SK".into(), AttributeValue::S(sk.to_string()));
+                item
+            });
+
+            let current = match item.get(attr) {
+                Some(AttributeValue::N(n)) => n.parse::<i64>().unwrap_or(0),
+                _ => 0,
+            };
+            let updated = current + delta;
+            item.insert(attr.to_string(), AttributeValue::N(updated.to_string()));
+            Ok(updated)
+        }
+
+        async fn query_range(
+            &self,
+            pk: &str,
+            sk_bound: Option<&str>,
+            direction: ScanDirection,
+            limit: usize,
+        ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+            let items = self.items.lock().unwrap();
+            let mut matching: Vec<(String, HashMap<String, Value>)> = items
+                .values()
+                .filter_map(|item| {
+                    let item_pk = item.get("PK").and_then(|v| {
+                        if let AttributeValue::S(s) = v { Some(s.as_str()) } else { None }
+                    });
+                    if item_pk != Some(pk) {
+                        return None;
+                    }
+                    let sk = item.get("SK").and_then(|v| {
+                        if let AttributeValue::S(s) = v { Some(s.clone()) } else { None }
+                    })?;
+                    let in_bound = match (sk_bound, direction) {
+                        (Some(bound), ScanDirection::Backward) => sk.as_str() < bound,
+                        (Some(bound), ScanDirection::Forward) => sk.as_str() > bound,
+                        (None, _) => true,
+                    };
+                    if !in_bound {
+                        return None;
+                    }
+                    let json_item = item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect();
+                    Some((sk, json_item))
+                })
+                .collect();
+
+            matching.sort_by(|(a, _), (b, _)| a.cmp(b));
+            if direction == ScanDirection::Backward {
+                matching.reverse();
+            }
+            matching.truncate(limit);
+
+            Ok(matching.into_iter().map(|(_, item)| item).collect())
+        }
+
+        async fn query_sk_compare(
+            &self,
+            pk: &str,
+            op: SkComparison,
+            sk: &str,
+        ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+            let items = self.items.lock().unwrap();
+            Ok(filter_sorted(&items, "PK", pk, "SK", |item_sk| op.matches(item_sk, sk)))
+        }
+
+        async fn query_sk_begins_with(
+            &self,
+            pk: &str,
+            prefix: &str,
+        ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+            let items = self.items.lock().unwrap();
+            Ok(filter_sorted(&items, "PK", pk, "SK", |item_sk| item_sk.starts_with(prefix)))
+        }
+
+        async fn query_sk_between(
+            &self,
+            pk: &str,
+            lo: &str,
+            hi: &str,
+        ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+            let items = self.items.lock().unwrap();
+            Ok(filter_sorted(&items, "PK", pk, "SK", |item_sk| item_sk >= lo && item_sk <= hi))
+        }
+
+        async fn query_gsi_sk_compare(
+            &self,
+            index_name: &str,
+            gsi_pk: &str,
+            op: SkComparison,
+            gsi_sk: &str,
+        ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+            let (pk_attr, sk_attr) = if index_name == "GSI2" {
+                ("GSI2PK", "GSI2SK")
+            } else {
+                ("GSI1PK", "GSI1SK")
+            };
+            let items = self.items.lock().unwrap();
+            Ok(filter_sorted(&items, pk_attr, gsi_pk, sk_attr, |item_sk| op.matches(item_sk, gsi_sk)))
+        }
+
+        async fn query_gsi_sk_begins_with(
+            &self,
+            index_name: &str,
+            gsi_pk: &str,
+            prefix: &str,
+        ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+            let (pk_attr, sk_attr) = if index_name == "GSI2" {
+                ("GSI2PK", "GSI2SK")
+            } else {
+                ("GSI1PK", "GSI1SK")
+            };
+            let items = self.items.lock().unwrap();
+            Ok(filter_sorted(&items, pk_attr, gsi_pk, sk_attr, |item_sk| item_sk.starts_with(prefix)))
+        }
+
+        async fn query_gsi_sk_between(
+            &self,
+            index_name: &str,
+            gsi_pk: &str,
+            lo: &str,
+            hi: &str,
+        ) -> Result<Vec<HashMap<String, Value>>, DynamoError> {
+            let (pk_attr, sk_attr) = if index_name == "GSI2" {
+                ("GSI2PK", "GSI2SK")
+            } else {
+                ("GSI1PK", "GSI1SK")
+            };
+            let items = self.items.lock().unwrap();
+            Ok(filter_sorted(&items, pk_attr, gsi_pk, sk_attr, |item_sk| item_sk >= lo && item_sk <= hi))
+        }
+
+        async fn query_page(
+            &self,
+            pk: &str,
+            exclusive_start_key: Option<KeyPair>,
+            page_size: Option<i32>,
+        ) -> Result<Page, DynamoError> {
+            let items = self.items.lock().unwrap();
+            let mut matching: Vec<(String, HashMap<String, Value>)> = items
+                .values()
+                .filter_map(|item| {
+                    let item_pk = item.get("PK").and_then(|v| {
+                        if let AttributeValue::S(s) = v { Some(s.as_str()) } else { None }
+                    });
+                    if item_pk != Some(pk) {
+                        return None;
+                    }
+                    let sk = item.get("SK").and_then(|v| {
+                        if let AttributeValue::S(s) = v { Some(s.clone()) } else { None }
+                    })?;
+                    let json_item = item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect();
+                    Some((sk, json_item))
+                })
+                .collect();
+            matching.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            if let Some(start) = &exclusive_start_key {
+                matching.retain(|(sk, _)| sk.as_str() > start.sk.as_str());
+            }
+
+            // Deterministically simulate DynamoDB's 1 MB-page truncation by
+            // honoring a caller-supplied page size instead: cut the page at
+            // `page_size` items and report the last included key as
+            // `last_key`, same as the real client does with
+            // `LastEvaluatedKey`.
+            let last_key = match page_size {
+                Some(size) if size > 0 && matching.len() > size as usize => {
+                    let cutoff_sk = matching[size as usize - 1].0.clone();
+                    matching.truncate(size as usize);
+                    Some(KeyPair { pk: pk.to_string(), sk: cutoff_sk })
+                }
+                _ => None,
+            };
+
+            Ok(Page {
+                items: matching.into_iter().map(|(_, item)| item).collect(),
+                last_key,
+            })
+        }
+
+        async fn query_gsi_with_sk_page(
+            &self,
+            index_name: &str,
+            gsi_pk: &str,
+            gsi_sk: &str,
+            exclusive_start_key: Option<GsiKeyPair>,
+            page_size: Option<i32>,
+        ) -> Result<GsiPage, DynamoError> {
+            let (pk_attr, sk_attr) = if index_name == "GSI2" {
+                ("GSI2PK", "GSI2SK")
+            } else {
+                ("GSI1PK", "GSI1SK")
+            };
+            let items = self.items.lock().unwrap();
+            let mut matching: Vec<(String, String, HashMap<String, Value>)> = items
+                .values()
+                .filter_map(|item| {
+                    let item_gsi_pk = item.get(pk_attr).and_then(|v| {
+                        if let AttributeValue::S(s) = v { Some(s.as_str()) } else { None }
+                    });
+                    let item_gsi_sk = item.get(sk_attr).and_then(|v| {
+                        if let AttributeValue::S(s) = v { Some(s.as_str()) } else { None }
+                    });
+                    if item_gsi_pk != Some(gsi_pk) || item_gsi_sk != Some(gsi_sk) {
+                        return None;
+                    }
+                    let pk = item.get("PK").and_then(|v| {
+                        if let AttributeValue::S(s) = v { Some(s.clone()) } else { None }
+                    })?;
+                    let sk = item.get("SK").and_then(|v| {
+                        if let AttributeValue::S(s) = v { Some(s.clone()) } else { None }
+                    })?;
+                    let json_item = item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect();
+                    Some((pk, sk, json_item))
+                })
+                .collect();
+            matching.sort_by(|(pk_a, sk_a, _), (pk_b, sk_b, _)| (pk_a, sk_a).cmp(&(pk_b, sk_b)));
+
+            if let Some(start) = &exclusive_start_key {
+                matching.retain(|(pk, sk, _)| (pk.as_str(), sk.as_str()) > (start.pk.as_str(), start.sk.as_str()));
+            }
+
+            let last_key = match page_size {
+                Some(size) if size > 0 && matching.len() > size as usize => {
+                    let (cutoff_pk, cutoff_sk, _) = matching[size as usize - 1].clone();
+                    matching.truncate(size as usize);
+                    Some(GsiKeyPair {
+                        pk: cutoff_pk,
+                        sk: cutoff_sk,
+                        gsi_pk: gsi_pk.to_string(),
+                        gsi_sk: gsi_sk.to_string(),
+                    })
+                }
+                _ => None,
+            };
+
+            Ok(GsiPage {
+                items: matching.into_iter().map(|(_, _, item)| item).collect(),
+                last_key,
+            })
+        }
+    }
+
+    /// Shared filter for the sort-key range methods above: items whose
+    /// `pk_attr` equals `pk` and whose `sk_attr` satisfies `predicate`,
+    /// returned ascending by sort key — the same order an unqualified
+    /// DynamoDB query comes back in.
+    fn filter_sorted(
+        items: &HashMap<String, HashMap<String, AttributeValue>>,
+        pk_attr: &str,
+        pk: &str,
+        sk_attr: &str,
+        predicate: impl Fn(&str) -> bool,
+    ) -> Vec<HashMap<String, Value>> {
+        let mut matching: Vec<(String, HashMap<String, Value>)> = items
+            .values()
+            .filter_map(|item| {
+                let item_pk = item.get(pk_attr).and_then(|v| {
+                    if let AttributeValue::S(s) = v { Some(s.as_str()) } else { None }
+                });
+                if item_pk != Some(pk) {
+                    return None;
+                }
+                let item_sk = item.get(sk_attr).and_then(|v| {
+                    if let AttributeValue::S(s) = v { Some(s.clone()) } else { None }
+                })?;
+                if !predicate(&item_sk) {
+                    return None;
+                }
+                let json_item = item.iter().map(|(k, v)| (k.clone(), av_to_json(v))).collect();
+                Some((item_sk, json_item))
+            })
+            .collect();
+
+        matching.sort_by(|(a, _), (b, _)| a.cmp(b));
+        matching.into_iter().map(|(_, item)| item).collect()
     }
 }
 
@@ -585,6 +1805,61 @@ mod tests {
         db.batch_delete_items(&[]).await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_increment_counter() {
+        let db = MockDynamo::new();
+        assert_eq!(db.increment_counter("channel-count:BOATS", "count", "value", 1).await.unwrap(), 1);
+        assert_eq!(db.increment_counter("channel-count:BOATS", "count", "value", 1).await.unwrap(), 2);
+        assert_eq!(db.increment_counter("channel-count:BOATS", "count", "value", -1).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_batch_put_items() {
+        let db = MockDynamo::new();
+        let puts = vec![
+            PutRequest { pk: "sub:user1".into(), sk: "ch0".into(), ..Default::default() },
+            PutRequest { pk: "sub:user1".into(), sk: "ch1".into(), ..Default::default() },
+        ];
+        db.batch_put_items(&puts).await.unwrap();
+
+        let items = db.query("sub:user1").await.unwrap();
+        assert_eq!(items.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_query_range_before_and_after() {
+        let db = MockDynamo::new();
+        for i in 0..5 {
+            db.put_item("message:ch1", &format!("{:02}", i), "", "", "", "", HashMap::new())
+                .await
+                .unwrap();
+        }
+
+        // Backward from "03" (exclusive) returns "02", "01", "00" descending.
+        let before = db
+            .query_range("message:ch1", Some("03"), ScanDirection::Backward, 10)
+            .await
+            .unwrap();
+        let before_sks: Vec<_> = before.iter().map(|i| i["SK"].as_str().unwrap()).collect();
+        assert_eq!(before_sks, vec!["02", "01", "00"]);
+
+        // Forward from "01" (exclusive) returns "02", "03", "04" ascending.
+        let after = db
+            .query_range("message:ch1", Some("01"), ScanDirection::Forward, 10)
+            .await
+            .unwrap();
+        let after_sks: Vec<_> = after.iter().map(|i| i["SK"].as_str().unwrap()).collect();
+        assert_eq!(after_sks, vec!["02", "03", "04"]);
+
+        // No bound + Backward + limit caps to the newest `limit` items.
+        let latest = db
+            .query_range("message:ch1", None, ScanDirection::Backward, 2)
+            .await
+            .unwrap();
+        let latest_sks: Vec<_> = latest.iter().map(|i| i["SK"].as_str().unwrap()).collect();
+        assert_eq!(latest_sks, vec!["04", "03"]);
+    }
+
     #[tokio::test]
     async fn test_put_item_with_gsi() {
         let db = MockDynamo::new();
@@ -598,4 +1873,188 @@ mod tests {
         let results = db.query_gsi("GSI1", "channel").await.unwrap();
         assert_eq!(results.len(), 1);
     }
+
+    #[tokio::test]
+    async fn test_put_item_if_version_requires_no_existing_item_when_expecting_none() {
+        let db = MockDynamo::new();
+        db.put_item("channel:u1", "abc123", "", "", "", "", HashMap::new())
+            .await
+            .unwrap();
+
+        let err = db
+            .put_item_if_version("channel:u1", "abc123", "", "", "", "", HashMap::new(), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DynamoError::ConditionalCheckFailed));
+    }
+
+    #[tokio::test]
+    async fn test_put_item_if_version_rejects_stale_expected_version() {
+        let db = MockDynamo::new();
+        let mut v1: HashMap<String, u64> = HashMap::new();
+        v1.insert("writer1".into(), 1);
+        let mut attrs = HashMap::new();
+        attrs.insert("version".into(), serde_json::to_value(&v1).unwrap());
+        db.put_item_if_version("channel:u1", "abc123", "", "", "", "", attrs, None)
+            .await
+            .unwrap();
+
+        // A second writer racing off the same stale (empty) expectation
+        // loses, the same as it would if it had raced a concurrent
+        // `channel-put` in the real handler.
+        let err = db
+            .put_item_if_version("channel:u1", "abc123", "", "", "", "", HashMap::new(), None)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DynamoError::ConditionalCheckFailed));
+
+        // The writer that read the current version first wins.
+        let mut v2 = v1.clone();
+        *v2.entry("writer1".into()).or_insert(0) += 1;
+        let mut attrs2 = HashMap::new();
+        attrs2.insert("version".into(), serde_json::to_value(&v2).unwrap());
+        db.put_item_if_version("channel:u1", "abc123", "", "", "", "", attrs2, Some(&v1))
+            .await
+            .unwrap();
+
+        let item = db.get_item("channel:u1", "abc123").await.unwrap().unwrap();
+        assert_eq!(item["version"], serde_json::to_value(&v2).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_query_sk_compare() {
+        let db = MockDynamo::new();
+        for i in 0..5 {
+            db.put_item("otp:+1555", &format!("{:02}", i), "", "", "", "", HashMap::new())
+                .await
+                .unwrap();
+        }
+
+        let lt = db.query_sk_compare("otp:+1555", SkComparison::LessThan, "02").await.unwrap();
+        assert_eq!(lt.iter().map(|i| i["SK"].as_str().unwrap()).collect::<Vec<_>>(), vec!["00", "01"]);
+
+        let gte = db.query_sk_compare("otp:+1555", SkComparison::GreaterThanOrEqual, "03").await.unwrap();
+        assert_eq!(gte.iter().map(|i| i["SK"].as_str().unwrap()).collect::<Vec<_>>(), vec!["03", "04"]);
+    }
+
+    #[tokio::test]
+    async fn test_query_sk_begins_with() {
+        let db = MockDynamo::new();
+        db.put_item("channel:c1", "msg#2026-01-01#1", "", "", "", "", HashMap::new()).await.unwrap();
+        db.put_item("channel:c1", "msg#2026-01-02#1", "", "", "", "", HashMap::new()).await.unwrap();
+        db.put_item("channel:c1", "meta#settings", "", "", "", "", HashMap::new()).await.unwrap();
+
+        let results = db.query_sk_begins_with("channel:c1", "msg#").await.unwrap();
+        assert_eq!(results.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_query_sk_between() {
+        let db = MockDynamo::new();
+        for i in 0..5 {
+            db.put_item("otp:+1555", &format!("{:02}", i), "", "", "", "", HashMap::new())
+                .await
+                .unwrap();
+        }
+
+        let results = db.query_sk_between("otp:+1555", "01", "03").await.unwrap();
+        assert_eq!(results.iter().map(|i| i["SK"].as_str().unwrap()).collect::<Vec<_>>(), vec!["01", "02", "03"]);
+    }
+
+    #[tokio::test]
+    async fn test_query_gsi_sk_compare_and_begins_with_and_between() {
+        let db = MockDynamo::new();
+        for (sk, gsi_sk) in [("a", "m00"), ("b", "m01"), ("c", "m02"), ("d", "x00")] {
+            db.put_item("channel:u1", sk, "channel", gsi_sk, "", "", HashMap::new())
+                .await
+                .unwrap();
+        }
+
+        let lt = db.query_gsi_sk_compare("GSI1", "channel", SkComparison::LessThan, "m02").await.unwrap();
+        assert_eq!(lt.len(), 2);
+
+        let prefix = db.query_gsi_sk_begins_with("GSI1", "channel", "m").await.unwrap();
+        assert_eq!(prefix.len(), 3);
+
+        let between = db.query_gsi_sk_between("GSI1", "channel", "m00", "m01").await.unwrap();
+        assert_eq!(between.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_query_page_honors_page_size_and_resumes_from_last_key() {
+        let db = MockDynamo::new();
+        for i in 0..5 {
+            db.put_item("sub:u1", &format!("{:02}", i), "", "", "", "", HashMap::new())
+                .await
+                .unwrap();
+        }
+
+        let first = db.query_page("sub:u1", None, Some(2)).await.unwrap();
+        assert_eq!(first.items.iter().map(|i| i["SK"].as_str().unwrap()).collect::<Vec<_>>(), vec!["00", "01"]);
+        let last_key = first.last_key.expect("page should be truncated");
+        assert_eq!(last_key.sk, "01");
+
+        let second = db.query_page("sub:u1", Some(last_key), Some(2)).await.unwrap();
+        assert_eq!(second.items.iter().map(|i| i["SK"].as_str().unwrap()).collect::<Vec<_>>(), vec!["02", "03"]);
+        let last_key = second.last_key.expect("page should be truncated");
+
+        let third = db.query_page("sub:u1", Some(last_key), Some(2)).await.unwrap();
+        assert_eq!(third.items.iter().map(|i| i["SK"].as_str().unwrap()).collect::<Vec<_>>(), vec!["04"]);
+        assert!(third.last_key.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_query_all_collects_every_page() {
+        let db = MockDynamo::new();
+        for i in 0..7 {
+            db.put_item("sub:u2", &format!("{:02}", i), "", "", "", "", HashMap::new())
+                .await
+                .unwrap();
+        }
+
+        // `query_all` always asks for unbounded pages (`page_size: None`),
+        // so with the mock this returns everything in a single page — it
+        // exercises the default trait method's loop-until-no-last-key exit
+        // condition, not the mock's page-size truncation.
+        let all = db.query_all("sub:u2").await.unwrap();
+        assert_eq!(all.len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_batch_get_items_skips_missing_keys() {
+        let db = MockDynamo::new();
+        db.put_item("channel:c1", "meta", "", "", "", "", HashMap::new()).await.unwrap();
+        db.put_item("channel:c2", "meta", "", "", "", "", HashMap::new()).await.unwrap();
+
+        let results = db
+            .batch_get_items(&[
+                KeyPair { pk: "channel:c1".into(), sk: "meta".into() },
+                KeyPair { pk: "channel:missing".into(), sk: "meta".into() },
+                KeyPair { pk: "channel:c2".into(), sk: "meta".into() },
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_transact_write_commits_puts_and_deletes_together() {
+        let db = MockDynamo::new();
+        db.put_item("otp:+1555", "old", "", "", "", "", HashMap::new()).await.unwrap();
+
+        db.transact_write(
+            &[PutRequest {
+                pk: "otp:+1555".into(),
+                sk: "new".into(),
+                ..Default::default()
+            }],
+            &[KeyPair { pk: "otp:+1555".into(), sk: "old".into() }],
+        )
+        .await
+        .unwrap();
+
+        assert!(db.get_item("otp:+1555", "old").await.unwrap().is_none());
+        assert!(db.get_item("otp:+1555", "new").await.unwrap().is_some());
+    }
 }
@@ -0,0 +1,170 @@
+//! Idempotent provisioning for the single-table layout every other
+//! module in this crate assumes: primary key `PK`/`SK` plus the `GSI1`
+//! (`GSI1PK`/`GSI1SK`) and `GSI2` (`GSI2PK`/`GSI2SK`) global secondary
+//! indexes. Like the standalone migrators split out of comparable
+//! services, this is meant to run ahead of the server — from a `migrate`
+//! binary during deployment — not as part of request handling, so a slow
+//! `CreateTable`/`UpdateTable` never blocks the Lambda/HTTP handler from
+//! booting.
+
+use std::time::Duration;
+
+use aws_sdk_dynamodb::types::{
+    AttributeDefinition, BillingMode, GlobalSecondaryIndex, GlobalSecondaryIndexUpdate, IndexStatus,
+    KeySchemaElement, KeyType, Projection, ProjectionType, ScalarAttributeType, TableStatus,
+};
+use aws_sdk_dynamodb::Client as DynamoDbClient;
+
+use crate::dynamo::DynamoError;
+
+/// Delay between `DescribeTable` polls while waiting for a table (or a
+/// newly added index) to reach `ACTIVE`.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The GSIs every table in this schema is expected to have, as
+/// `(index_name, pk_attr, sk_attr)`.
+const EXPECTED_INDEXES: [(&str, &str, &str); 2] = [
+    ("GSI1", "GSI1PK", "GSI1SK"),
+    ("GSI2", "GSI2PK", "GSI2SK"),
+];
+
+/// Create `table_name` with the `PK`/`SK` primary key and both GSIs if it
+/// doesn't exist yet, or add whichever of the two GSIs are missing if it
+/// does. Safe to call on every deployment — a table that already matches
+/// the expected schema is left untouched. Returns once the table (and
+/// any newly added index) is `ACTIVE`.
+pub async fn ensure_schema(client: &DynamoDbClient, table_name: &str) -> Result<(), DynamoError> {
+    match describe_indexes(client, table_name).await? {
+        None => {
+            create_table(client, table_name).await?;
+            wait_until_active(client, table_name).await?;
+        }
+        Some(existing) => {
+            for (index_name, pk_attr, sk_attr) in EXPECTED_INDEXES {
+                if existing.iter().any(|name| name == index_name) {
+                    continue;
+                }
+                add_index(client, table_name, index_name, pk_attr, sk_attr).await?;
+                wait_until_active(client, table_name).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `Some(names of the table's existing GSIs)`, or `None` if `table_name`
+/// doesn't exist yet.
+async fn describe_indexes(client: &DynamoDbClient, table_name: &str) -> Result<Option<Vec<String>>, DynamoError> {
+    match client.describe_table().table_name(table_name).send().await {
+        Ok(out) => {
+            let indexes = out
+                .table()
+                .and_then(|t| t.global_secondary_indexes())
+                .map(|gsis| gsis.iter().filter_map(|g| g.index_name().map(str::to_string)).collect())
+                .unwrap_or_default();
+            Ok(Some(indexes))
+        }
+        Err(e) => {
+            let not_found = e
+                .as_service_error()
+                .map(|se| se.is_resource_not_found_exception())
+                .unwrap_or(false);
+            if not_found {
+                Ok(None)
+            } else {
+                Err(DynamoError::Sdk(e.to_string()))
+            }
+        }
+    }
+}
+
+async fn create_table(client: &DynamoDbClient, table_name: &str) -> Result<(), DynamoError> {
+    client
+        .create_table()
+        .table_name(table_name)
+        .billing_mode(BillingMode::PayPerRequest)
+        .attribute_definitions(attr_def("PK"))
+        .attribute_definitions(attr_def("SK"))
+        .attribute_definitions(attr_def("GSI1PK"))
+        .attribute_definitions(attr_def("GSI1SK"))
+        .attribute_definitions(attr_def("GSI2PK"))
+        .attribute_definitions(attr_def("GSI2SK"))
+        .key_schema(key_elem("PK", KeyType::Hash))
+        .key_schema(key_elem("SK", KeyType::Range))
+        .global_secondary_indexes(gsi("GSI1", "GSI1PK", "GSI1SK"))
+        .global_secondary_indexes(gsi("GSI2", "GSI2PK", "GSI2SK"))
+        .send()
+        .await
+        .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+    Ok(())
+}
+
+async fn add_index(
+    client: &DynamoDbClient,
+    table_name: &str,
+    index_name: &str,
+    pk_attr: &str,
+    sk_attr: &str,
+) -> Result<(), DynamoError> {
+    client
+        .update_table()
+        .table_name(table_name)
+        .attribute_definitions(attr_def(pk_attr))
+        .attribute_definitions(attr_def(sk_attr))
+        .global_secondary_index_updates(
+            GlobalSecondaryIndexUpdate::builder()
+                .create(gsi(index_name, pk_attr, sk_attr))
+                .build(),
+        )
+        .send()
+        .await
+        .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+    Ok(())
+}
+
+/// Poll `DescribeTable` until the table itself and every one of its GSIs
+/// report `ACTIVE`.
+async fn wait_until_active(client: &DynamoDbClient, table_name: &str) -> Result<(), DynamoError> {
+    loop {
+        let out = client
+            .describe_table()
+            .table_name(table_name)
+            .send()
+            .await
+            .map_err(|e| DynamoError::Sdk(e.to_string()))?;
+
+        let table_active = out.table().and_then(|t| t.table_status()) == Some(&TableStatus::Active);
+        let indexes_active = out
+            .table()
+            .and_then(|t| t.global_secondary_indexes())
+            .map(|gsis| gsis.iter().all(|g| g.index_status() == Some(&IndexStatus::Active)))
+            .unwrap_or(true);
+
+        if table_active && indexes_active {
+            return Ok(());
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+fn attr_def(name: &str) -> AttributeDefinition {
+    AttributeDefinition::builder()
+        .attribute_name(name)
+        .attribute_type(ScalarAttributeType::S)
+        .build()
+        .unwrap()
+}
+
+fn key_elem(name: &str, key_type: KeyType) -> KeySchemaElement {
+    KeySchemaElement::builder().attribute_name(name).key_type(key_type).build().unwrap()
+}
+
+fn gsi(index_name: &str, pk_attr: &str, sk_attr: &str) -> GlobalSecondaryIndex {
+    GlobalSecondaryIndex::builder()
+        .index_name(index_name)
+        .key_schema(key_elem(pk_attr, KeyType::Hash))
+        .key_schema(key_elem(sk_attr, KeyType::Range))
+        .projection(Projection::builder().projection_type(ProjectionType::All).build())
+        .build()
+        .unwrap()
+}
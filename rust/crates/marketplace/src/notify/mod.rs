@@ -1,7 +1,8 @@
 pub mod sns;
+pub mod sns_inbound;
 pub mod ses;
 pub mod webpush;
 
 pub use sns::SnsApi;
-pub use ses::SesApi;
+pub use ses::{EmailMessage, SesApi};
 pub use webpush::WebPushKeys;
@@ -1,21 +1,76 @@
 use async_trait::async_trait;
+use serde_json::Value;
 
 #[derive(Debug, thiserror::Error)]
 pub enum SesError {
     #[error("ses send to {0}: {1}")]
     Send(String, String),
+    #[error("ses send to {0}: templated send not supported by this client")]
+    TemplateUnsupported(String),
+}
+
+/// An email to send, with optional HTML and plaintext bodies (at least one
+/// should be set) and an optional reply-to address. When both bodies are
+/// present, [`SesClient`] sends a `multipart/alternative` body so mail
+/// clients without HTML rendering still get the plaintext fallback.
+#[derive(Debug, Clone, Default)]
+pub struct EmailMessage {
+    pub subject: String,
+    pub text_body: Option<String>,
+    pub html_body: Option<String>,
+    pub reply_to: Option<String>,
+}
+
+impl EmailMessage {
+    /// A plaintext-only message — what the old `send_email` signature sent.
+    pub fn text(subject: impl Into<String>, body: impl Into<String>) -> Self {
+        EmailMessage {
+            subject: subject.into(),
+            text_body: Some(body.into()),
+            ..Default::default()
+        }
+    }
 }
 
 /// Trait abstracting SES operations for testing.
 #[async_trait]
 pub trait SesApi: Send + Sync {
+    /// Send `message`, choosing a plaintext, HTML, or multipart/alternative
+    /// body depending on which of its fields are set.
+    async fn send_message(
+        &self,
+        from_addr: &str,
+        to_addr: &str,
+        message: &EmailMessage,
+    ) -> Result<(), SesError>;
+
+    /// Send a plaintext email. Kept for existing call sites — a thin
+    /// wrapper over [`send_message`](Self::send_message).
     async fn send_email(
         &self,
         from_addr: &str,
         to_addr: &str,
         subject: &str,
         body: &str,
-    ) -> Result<(), SesError>;
+    ) -> Result<(), SesError> {
+        self.send_message(from_addr, to_addr, &EmailMessage::text(subject, body))
+            .await
+    }
+
+    /// Send an email rendered server-side from an SES template plus
+    /// substitution `data`, for notification emails whose layout lives in
+    /// SES rather than this codebase. Not every implementation supports
+    /// this (e.g. a mock used only for plaintext assertions); the default
+    /// returns [`SesError::TemplateUnsupported`].
+    async fn send_templated(
+        &self,
+        _from_addr: &str,
+        to_addr: &str,
+        _template_name: &str,
+        _data: &Value,
+    ) -> Result<(), SesError> {
+        Err(SesError::TemplateUnsupported(to_addr.into()))
+    }
 }
 
 /// Real AWS SES v2 client.
@@ -33,24 +88,63 @@ impl SesClient {
 
 #[async_trait]
 impl SesApi for SesClient {
-    async fn send_email(
+    async fn send_message(
         &self,
         from_addr: &str,
         to_addr: &str,
-        subject: &str,
-        body: &str,
+        message: &EmailMessage,
     ) -> Result<(), SesError> {
         use aws_sdk_sesv2::types::{Body, Content, Destination, EmailContent, Message};
 
-        let dest = Destination::builder().to_addresses(to_addr).build();
-        let subject_content = Content::builder().data(subject).build().unwrap();
-        let body_content = Content::builder().data(body).build().unwrap();
+        let subject_content = Content::builder().data(&message.subject).build().unwrap();
+
+        let mut body_builder = Body::builder();
+        if let Some(text) = &message.text_body {
+            body_builder = body_builder.text(Content::builder().data(text).build().unwrap());
+        }
+        if let Some(html) = &message.html_body {
+            body_builder = body_builder.html(Content::builder().data(html).build().unwrap());
+        }
+
         let msg = Message::builder()
             .subject(subject_content)
-            .body(Body::builder().text(body_content).build())
+            .body(body_builder.build())
             .build();
         let content = EmailContent::builder().simple(msg).build();
 
+        let mut req = self
+            .client
+            .send_email()
+            .from_email_address(from_addr)
+            .destination(Destination::builder().to_addresses(to_addr).build())
+            .content(content);
+        if let Some(reply_to) = &message.reply_to {
+            req = req.reply_to_addresses(reply_to);
+        }
+
+        req.send()
+            .await
+            .map_err(|e| SesError::Send(to_addr.into(), e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn send_templated(
+        &self,
+        from_addr: &str,
+        to_addr: &str,
+        template_name: &str,
+        data: &Value,
+    ) -> Result<(), SesError> {
+        use aws_sdk_sesv2::types::{Destination, EmailContent, Template};
+
+        let dest = Destination::builder().to_addresses(to_addr).build();
+        let template = Template::builder()
+            .template_name(template_name)
+            .template_data(data.to_string())
+            .build();
+        let content = EmailContent::builder().template(template).build();
+
         self.client
             .send_email()
             .from_email_address(from_addr)
@@ -69,31 +163,73 @@ pub mod mock {
     use super::*;
     use std::sync::Mutex;
 
+    /// The body variant a [`MockSes::send_message`] call was given, so
+    /// tests can assert HTML vs text (vs both) was chosen without parsing
+    /// MIME output.
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum RecordedBody {
+        Text(String),
+        Html(String),
+        Multipart { text: String, html: String },
+    }
+
     pub struct MockSes {
         pub emails: Mutex<Vec<(String, String)>>,
+        pub messages: Mutex<Vec<(String, String, RecordedBody)>>,
+        pub templated: Mutex<Vec<(String, String, Value)>>,
     }
 
     impl MockSes {
         pub fn new() -> Self {
             Self {
                 emails: Mutex::new(Vec::new()),
+                messages: Mutex::new(Vec::new()),
+                templated: Mutex::new(Vec::new()),
             }
         }
     }
 
     #[async_trait]
     impl SesApi for MockSes {
-        async fn send_email(
+        async fn send_message(
             &self,
             _from_addr: &str,
             to_addr: &str,
-            subject: &str,
-            _body: &str,
+            message: &EmailMessage,
         ) -> Result<(), SesError> {
             self.emails
                 .lock()
                 .unwrap()
-                .push((to_addr.into(), subject.into()));
+                .push((to_addr.into(), message.subject.clone()));
+
+            let body = match (&message.text_body, &message.html_body) {
+                (Some(text), Some(html)) => RecordedBody::Multipart {
+                    text: text.clone(),
+                    html: html.clone(),
+                },
+                (Some(text), None) => RecordedBody::Text(text.clone()),
+                (None, Some(html)) => RecordedBody::Html(html.clone()),
+                (None, None) => RecordedBody::Text(String::new()),
+            };
+            self.messages
+                .lock()
+                .unwrap()
+                .push((to_addr.into(), message.subject.clone(), body));
+
+            Ok(())
+        }
+
+        async fn send_templated(
+            &self,
+            _from_addr: &str,
+            to_addr: &str,
+            template_name: &str,
+            data: &Value,
+        ) -> Result<(), SesError> {
+            self.templated
+                .lock()
+                .unwrap()
+                .push((to_addr.into(), template_name.into(), data.clone()));
             Ok(())
         }
     }
@@ -101,7 +237,7 @@ pub mod mock {
 
 #[cfg(test)]
 mod tests {
-    use super::mock::MockSes;
+    use super::mock::{MockSes, RecordedBody};
     use super::*;
 
     #[tokio::test]
@@ -115,4 +251,49 @@ mod tests {
         assert_eq!(emails[0].0, "user@example.com");
         assert_eq!(emails[0].1, "Your OTP");
     }
+
+    #[tokio::test]
+    async fn test_send_email_records_text_body() {
+        let ses = MockSes::new();
+        ses.send_email("noreply@example.com", "user@example.com", "Your OTP", "Code: 654321")
+            .await
+            .unwrap();
+        let messages = ses.messages.lock().unwrap();
+        assert_eq!(messages[0].2, RecordedBody::Text("Code: 654321".into()));
+    }
+
+    #[tokio::test]
+    async fn test_send_message_multipart_records_both_bodies() {
+        let ses = MockSes::new();
+        let message = EmailMessage {
+            subject: "New message".into(),
+            text_body: Some("plain".into()),
+            html_body: Some("<b>html</b>".into()),
+            reply_to: None,
+        };
+        ses.send_message("noreply@example.com", "user@example.com", &message)
+            .await
+            .unwrap();
+        let messages = ses.messages.lock().unwrap();
+        assert_eq!(
+            messages[0].2,
+            RecordedBody::Multipart {
+                text: "plain".into(),
+                html: "<b>html</b>".into(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_templated_records_template_name_and_data() {
+        let ses = MockSes::new();
+        let data = serde_json::json!({"code": "654321"});
+        ses.send_templated("noreply@example.com", "user@example.com", "otp-email", &data)
+            .await
+            .unwrap();
+        let templated = ses.templated.lock().unwrap();
+        assert_eq!(templated[0].0, "user@example.com");
+        assert_eq!(templated[0].1, "otp-email");
+        assert_eq!(templated[0].2, data);
+    }
 }
@@ -4,12 +4,49 @@ use async_trait::async_trait;
 pub enum SnsError {
     #[error("sns publish to {0}: {1}")]
     Publish(String, String),
+    #[error("sns publish to topic {0}: {1}")]
+    PublishTopic(String, String),
+}
+
+/// A single SNS message attribute, used for subscription filter policies.
+/// Maps onto `aws_sdk_sns::types::MessageAttributeValue`.
+#[derive(Debug, Clone)]
+pub struct MessageAttribute {
+    pub data_type: MessageAttributeType,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum MessageAttributeType {
+    String,
+    Number,
+    Binary,
+}
+
+impl MessageAttributeType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MessageAttributeType::String => "String",
+            MessageAttributeType::Number => "Number",
+            MessageAttributeType::Binary => "Binary",
+        }
+    }
 }
 
 /// Trait abstracting SNS operations for testing.
 #[async_trait]
 pub trait SnsApi: Send + Sync {
     async fn send_sms(&self, phone: &str, message: &str) -> Result<(), SnsError>;
+
+    /// Publish `message` to an SNS topic, the core SNS primitive for
+    /// pub/sub fan-out. `attributes` are attached so subscribers can use
+    /// filter policies to select which messages they receive.
+    async fn publish_topic(
+        &self,
+        topic_arn: &str,
+        message: &str,
+        attributes: &[(String, MessageAttribute)],
+    ) -> Result<(), SnsError>;
 }
 
 /// Real AWS SNS client.
@@ -37,6 +74,27 @@ impl SnsApi for SnsClient {
             .map_err(|e| SnsError::Publish(phone.into(), e.to_string()))?;
         Ok(())
     }
+
+    async fn publish_topic(
+        &self,
+        topic_arn: &str,
+        message: &str,
+        attributes: &[(String, MessageAttribute)],
+    ) -> Result<(), SnsError> {
+        let mut req = self.client.publish().topic_arn(topic_arn).message(message);
+        for (name, attr) in attributes {
+            let value = aws_sdk_sns::types::MessageAttributeValue::builder()
+                .data_type(attr.data_type.as_str())
+                .string_value(&attr.value)
+                .build()
+                .map_err(|e| SnsError::PublishTopic(topic_arn.into(), e.to_string()))?;
+            req = req.message_attributes(name, value);
+        }
+        req.send()
+            .await
+            .map_err(|e| SnsError::PublishTopic(topic_arn.into(), e.to_string()))?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -46,12 +104,14 @@ pub mod mock {
 
     pub struct MockSns {
         pub messages: Mutex<Vec<(String, String)>>,
+        pub topic_publishes: Mutex<Vec<(String, String, Vec<(String, MessageAttribute)>)>>,
     }
 
     impl MockSns {
         pub fn new() -> Self {
             Self {
                 messages: Mutex::new(Vec::new()),
+                topic_publishes: Mutex::new(Vec::new()),
             }
         }
     }
@@ -65,6 +125,20 @@ pub mod mock {
                 .push((phone.into(), message.into()));
             Ok(())
         }
+
+        async fn publish_topic(
+            &self,
+            topic_arn: &str,
+            message: &str,
+            attributes: &[(String, MessageAttribute)],
+        ) -> Result<(), SnsError> {
+            self.topic_publishes.lock().unwrap().push((
+                topic_arn.into(),
+                message.into(),
+                attributes.to_vec(),
+            ));
+            Ok(())
+        }
     }
 }
 
@@ -114,7 +114,24 @@ async fn handle_account_delete(deps: &Deps, args: Value) -> Result<ToolResult, M
         }
     }
 
-    // 4. Batch delete everything.
+    // 4. Delete user's blocks.
+    match deps.db.query(&format!("block:{}", user_id)).await {
+        Ok(blocks) => {
+            for b in &blocks {
+                if let (Some(pk), Some(sk)) = (
+                    b.get("PK").and_then(|v| v.as_str()),
+                    b.get("SK").and_then(|v| v.as_str()),
+                ) {
+                    all_pairs.push(KeyPair { pk: pk.into(), sk: sk.into() });
+                }
+            }
+        }
+        Err(e) => {
+            tracing::error!("account-delete query blocks: {}", e);
+        }
+    }
+
+    // 5. Batch delete everything.
     if !all_pairs.is_empty() {
         if let Err(e) = deps.db.batch_delete_items(&all_pairs).await {
             tracing::error!("account-delete batch delete: {}", e);
@@ -0,0 +1,268 @@
+//! OAuth 2.1 bearer-token authorization for the HTTP transport: extract
+//! `Authorization: Bearer <token>` from the request, verify it against a
+//! pluggable [`TokenValidator`] (a static allow-list, an introspection
+//! endpoint, or JWT/JWKS verification — this crate only defines the
+//! trait), and reject anything else with `401` plus a `WWW-Authenticate`
+//! header naming the authorization server's metadata, per RFC 9728.
+//!
+//! Wrap a router built by [`crate::transport_http::http_router`] (or
+//! [`crate::transport_http::http_router_with_sessions`]) in
+//! [`with_bearer_auth`] to turn this on; without it the transport is
+//! unauthenticated, same as before this existed.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::extract::{Request, State};
+use axum::http::{header, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+/// How many bytes of a `POST /mcp` body [`with_bearer_auth`] will buffer to
+/// peek at `method` when checking for an unauthenticated `initialize`
+/// probe. Bodies larger than this are never treated as a probe — they fall
+/// through to the ordinary bearer check — so a handful of oversized
+/// requests can't be used to skip buffering entirely.
+const INITIALIZE_PROBE_PEEK_LIMIT: usize = 64 * 1024;
+
+/// The verified identity behind a request. [`with_bearer_auth`] inserts
+/// one into the request's extensions on a successful [`TokenValidator`]
+/// check, for downstream code to pull out with `Extension<AuthContext>`
+/// instead of re-deriving identity from the request body.
+#[derive(Debug, Clone)]
+pub struct AuthContext {
+    pub subject: String,
+    pub scopes: Vec<String>,
+}
+
+impl AuthContext {
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope)
+    }
+}
+
+/// Verifies a bearer token and resolves it to the identity it authorizes,
+/// or `None` if it's invalid, expired, or revoked. Implement this against
+/// whatever an authorization server actually is in a given deployment —
+/// a static allow-list (see [`StaticTokenValidator`]), a call out to an
+/// RFC 7662 introspection endpoint, or local JWT signature verification
+/// against a configured JWKS.
+#[async_trait]
+pub trait TokenValidator: Send + Sync {
+    async fn validate(&self, token: &str) -> Option<AuthContext>;
+}
+
+/// A fixed `{token: AuthContext}` map. Useful for local development, CI,
+/// and single-tenant deployments that mint their own opaque tokens out of
+/// band; anything needing revocation or expiry should implement
+/// [`TokenValidator`] against a real authorization server instead.
+#[derive(Default)]
+pub struct StaticTokenValidator {
+    tokens: HashMap<String, AuthContext>,
+}
+
+impl StaticTokenValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(mut self, token: impl Into<String>, subject: impl Into<String>, scopes: Vec<String>) -> Self {
+        self.tokens.insert(token.into(), AuthContext { subject: subject.into(), scopes });
+        self
+    }
+}
+
+#[async_trait]
+impl TokenValidator for StaticTokenValidator {
+    async fn validate(&self, token: &str) -> Option<AuthContext> {
+        self.tokens.get(token).cloned()
+    }
+}
+
+/// Configuration for [`with_bearer_auth`].
+pub struct AuthConfig {
+    validator: Arc<dyn TokenValidator>,
+    /// URL of the protected-resource metadata document (RFC 9728) to
+    /// advertise in `WWW-Authenticate` on a `401`, so a client that
+    /// doesn't already have a token knows which authorization server to
+    /// start the OAuth 2.1 flow with.
+    resource_metadata_url: String,
+    /// Let a bodyless-of-credentials `initialize` call through even
+    /// without a bearer token, so a client can probe server capabilities
+    /// before it has completed authorization. Everything past
+    /// `initialize` still requires a valid token.
+    allow_unauthenticated_initialize: bool,
+}
+
+impl AuthConfig {
+    pub fn new(validator: Arc<dyn TokenValidator>, resource_metadata_url: impl Into<String>) -> Self {
+        Self {
+            validator,
+            resource_metadata_url: resource_metadata_url.into(),
+            allow_unauthenticated_initialize: false,
+        }
+    }
+
+    pub fn allow_unauthenticated_initialize(mut self, allow: bool) -> Self {
+        self.allow_unauthenticated_initialize = allow;
+        self
+    }
+}
+
+/// Wrap `router` so every request must carry a valid `Authorization:
+/// Bearer <token>` header, verified against `config`'s [`TokenValidator`].
+/// On success the resolved [`AuthContext`] is inserted into the request's
+/// extensions before it reaches the inner router. On failure the response
+/// is `401` with `WWW-Authenticate` pointing at the configured resource
+/// metadata URL, per RFC 9728 — never a silent pass-through.
+pub fn with_bearer_auth(router: axum::Router, config: Arc<AuthConfig>) -> axum::Router {
+    router.layer(axum::middleware::from_fn_with_state(config, require_bearer_auth))
+}
+
+async fn require_bearer_auth(State(config): State<Arc<AuthConfig>>, mut req: Request, next: Next) -> Response {
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    if let Some(token) = token {
+        return match config.validator.validate(token).await {
+            Some(ctx) => {
+                req.extensions_mut().insert(ctx);
+                next.run(req).await
+            }
+            None => unauthorized(&config),
+        };
+    }
+
+    if config.allow_unauthenticated_initialize && is_initialize_probe(&mut req).await {
+        return next.run(req).await;
+    }
+
+    unauthorized(&config)
+}
+
+/// Peek at a `POST /mcp` body to see whether it's an `initialize` call,
+/// without consuming it for the handler downstream — buffers the body,
+/// inspects `method`, then puts the same bytes back so `Json<Value>`
+/// extraction further down the stack sees an untouched request.
+async fn is_initialize_probe(req: &mut Request) -> bool {
+    if req.method() != Method::POST {
+        return false;
+    }
+
+    let body = std::mem::replace(req.body_mut(), axum::body::Body::empty());
+    let bytes = match axum::body::to_bytes(body, INITIALIZE_PROBE_PEEK_LIMIT).await {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+
+    let is_initialize = serde_json::from_slice::<serde_json::Value>(&bytes)
+        .ok()
+        .and_then(|v| v.get("method").and_then(|m| m.as_str().map(str::to_string)))
+        .is_some_and(|method| method == "initialize");
+
+    *req.body_mut() = axum::body::Body::from(bytes);
+    is_initialize
+}
+
+fn unauthorized(config: &AuthConfig) -> Response {
+    let mut response = (StatusCode::UNAUTHORIZED, "missing or invalid bearer token").into_response();
+    let value = format!(
+        r#"Bearer error="invalid_token", resource_metadata="{}""#,
+        config.resource_metadata_url
+    );
+    if let Ok(value) = value.parse() {
+        response.headers_mut().insert(header::WWW_AUTHENTICATE, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request as HttpRequest;
+    use axum::routing::{get, post};
+    use axum::Router;
+    use tower::ServiceExt;
+
+    fn protected_router() -> Router {
+        let inner = Router::new()
+            .route("/mcp", post(|| async { "ok" }))
+            .route("/healthz", get(|| async { "ok" }));
+        let config = Arc::new(
+            AuthConfig::new(
+                Arc::new(StaticTokenValidator::new().insert("good-token", "user-1", vec!["mcp.read".into()])),
+                "https://auth.example.com/.well-known/oauth-protected-resource",
+            )
+            .allow_unauthenticated_initialize(true),
+        );
+        with_bearer_auth(inner, config)
+    }
+
+    #[tokio::test]
+    async fn test_missing_token_is_rejected() {
+        let app = protected_router();
+        let req = HttpRequest::builder().method("GET").uri("/healthz").body(Body::empty()).unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert!(resp.headers().contains_key(header::WWW_AUTHENTICATE));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_token_is_rejected() {
+        let app = protected_router();
+        let req = HttpRequest::builder()
+            .method("GET")
+            .uri("/healthz")
+            .header("authorization", "Bearer not-a-real-token")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_valid_token_is_accepted() {
+        let app = protected_router();
+        let req = HttpRequest::builder()
+            .method("GET")
+            .uri("/healthz")
+            .header("authorization", "Bearer good-token")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_unauthenticated_initialize_probe_is_allowed() {
+        let app = protected_router();
+        let body = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"});
+        let req = HttpRequest::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_unauthenticated_non_initialize_call_is_rejected() {
+        let app = protected_router();
+        let body = serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"});
+        let req = HttpRequest::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+}
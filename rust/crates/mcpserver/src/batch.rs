@@ -0,0 +1,252 @@
+//! JMAP-style batching: submit several tool calls in one `tools/callBatch`
+//! request and run them in order, with later calls able to reference
+//! fields from earlier calls' results.
+//!
+//! Each sub-call is dispatched through the server's ordinary `tools/call`
+//! path — no direct access to tool handlers is needed, so this composes
+//! with any handler registered the normal way.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::server::Server;
+use crate::types::JsonRpcRequest;
+
+/// One call in a `tools/callBatch` request.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchCall {
+    /// Client-supplied id, echoed back on the matching [`BatchCallResult`]
+    /// so callers can correlate responses.
+    pub id: Value,
+    pub name: String,
+    #[serde(default)]
+    pub arguments: Value,
+    /// Whether the rest of the batch runs if this call's tool returns an
+    /// error. Defaults to `stop`, matching a plain (non-batched) sequence
+    /// of calls where a client would bail out on the first failure.
+    #[serde(default, rename = "onError")]
+    pub on_error: OnError,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnError {
+    #[default]
+    Stop,
+    Continue,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchCallResult {
+    pub id: Value,
+    pub result: Value,
+}
+
+/// Run `calls` against `server` in order, substituting `#<json-pointer>`
+/// back-references in each call's arguments before dispatching it, and
+/// stopping early on error unless that call's `onError` is `continue`.
+pub async fn run_batch(server: &Server, calls: Vec<BatchCall>) -> Vec<BatchCallResult> {
+    let mut results = Vec::with_capacity(calls.len());
+    let mut resolved_results: Vec<Value> = Vec::with_capacity(calls.len());
+
+    for call in calls {
+        let arguments = resolve_refs(call.arguments, &resolved_results);
+
+        let req = JsonRpcRequest {
+            jsonrpc: "2.0".into(),
+            id: Some(json!(0)),
+            method: "tools/call".into(),
+            params: Some(json!({"name": call.name, "arguments": arguments})),
+        };
+
+        let resp = server.handle(req).await;
+        let (result, is_error) = match (resp.result, resp.error) {
+            (Some(r), _) => (r, false),
+            (None, Some(e)) => (json!({"error": e.message}), true),
+            (None, None) => (Value::Null, false),
+        };
+
+        resolved_results.push(extract_value(&result));
+        results.push(BatchCallResult { id: call.id, result });
+
+        if is_error && matches!(call.on_error, OnError::Stop) {
+            break;
+        }
+    }
+
+    results
+}
+
+/// Tool results come back as `{"content":[{"type":"text","text":"..."}]}`
+/// with the actual payload JSON-encoded in `text` (see e.g. `channel-put`'s
+/// `{"channelId": ...}`). Back-references need that unwrapped payload, not
+/// the content-block envelope around it.
+fn extract_value(result: &Value) -> Value {
+    result
+        .pointer("/content/0/text")
+        .and_then(|v| v.as_str())
+        .map(|text| serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.into())))
+        .unwrap_or_else(|| result.clone())
+}
+
+/// Replace every string of the form `#<json-pointer>` anywhere in `args`
+/// with the first match for that pointer found scanning `previous_results`
+/// from most to least recent. A reference that resolves against nothing
+/// (typo'd field, pointer into a call that hasn't run yet) is left as the
+/// literal string, so it surfaces in the tool's validation error instead of
+/// silently becoming null.
+fn resolve_refs(args: Value, previous_results: &[Value]) -> Value {
+    match args {
+        Value::String(s) => {
+            if let Some(pointer) = s.strip_prefix('#') {
+                let pointer = if pointer.starts_with('/') {
+                    pointer.to_string()
+                } else {
+                    format!("/{}", pointer)
+                };
+                if let Some(resolved) = previous_results
+                    .iter()
+                    .rev()
+                    .find_map(|r| r.pointer(&pointer))
+                {
+                    return resolved.clone();
+                }
+            }
+            Value::String(s)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|v| resolve_refs(v, previous_results))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(k, v)| (k, resolve_refs(v, previous_results)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::{FnToolHandler, ToolResult};
+    use crate::types::{error_result, text_result, McpError};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn test_server() -> Server {
+        let tools_json = r#"[
+            {"name":"make-thing","description":"makes a thing","inputSchema":{"type":"object","properties":{}}},
+            {"name":"use-thing","description":"uses a thing id","inputSchema":{"type":"object","properties":{"thingId":{"type":"string"}},"required":["thingId"]}},
+            {"name":"always-fails","description":"always errors","inputSchema":{"type":"object","properties":{}}}
+        ]"#;
+
+        let mut srv = Server::builder()
+            .tools_json(tools_json.as_bytes())
+            .server_info("batch-test", "0.1.0")
+            .build();
+
+        let counter = Arc::new(AtomicUsize::new(0));
+        srv.handle_tool(
+            "make-thing",
+            FnToolHandler::new(move |_args: Value| {
+                let counter = counter.clone();
+                async move {
+                    let n = counter.fetch_add(1, Ordering::SeqCst);
+                    Ok(text_result(
+                        &json!({"thingId": format!("thing-{}", n)}).to_string(),
+                    ))
+                }
+            }),
+        );
+        srv.handle_tool(
+            "use-thing",
+            FnToolHandler::new(move |args: Value| async move {
+                let thing_id = args.get("thingId").and_then(|v| v.as_str()).unwrap_or("");
+                Ok(text_result(&format!("used {}", thing_id)))
+            }),
+        );
+        srv.handle_tool(
+            "always-fails",
+            FnToolHandler::new(move |_args: Value| async move {
+                Ok::<ToolResult, McpError>(error_result("boom"))
+            }),
+        );
+
+        srv
+    }
+
+    #[tokio::test]
+    async fn test_batch_resolves_back_reference() {
+        let srv = test_server();
+        let calls = vec![
+            BatchCall {
+                id: json!("c1"),
+                name: "make-thing".into(),
+                arguments: json!({}),
+                on_error: OnError::Stop,
+            },
+            BatchCall {
+                id: json!("c2"),
+                name: "use-thing".into(),
+                arguments: json!({"thingId": "#thingId"}),
+                on_error: OnError::Stop,
+            },
+        ];
+
+        let results = run_batch(&srv, calls).await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, json!("c1"));
+        assert_eq!(results[1].id, json!("c2"));
+
+        let used_text = results[1].result["content"][0]["text"].as_str().unwrap();
+        assert_eq!(used_text, "used thing-0");
+    }
+
+    #[tokio::test]
+    async fn test_batch_stops_on_error_by_default() {
+        let srv = test_server();
+        let calls = vec![
+            BatchCall {
+                id: json!(1),
+                name: "always-fails".into(),
+                arguments: json!({}),
+                on_error: OnError::Stop,
+            },
+            BatchCall {
+                id: json!(2),
+                name: "make-thing".into(),
+                arguments: json!({}),
+                on_error: OnError::Stop,
+            },
+        ];
+
+        let results = run_batch(&srv, calls).await;
+        assert_eq!(results.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_batch_continues_past_error_when_requested() {
+        let srv = test_server();
+        let calls = vec![
+            BatchCall {
+                id: json!(1),
+                name: "always-fails".into(),
+                arguments: json!({}),
+                on_error: OnError::Continue,
+            },
+            BatchCall {
+                id: json!(2),
+                name: "make-thing".into(),
+                arguments: json!({}),
+                on_error: OnError::Stop,
+            },
+        ];
+
+        let results = run_batch(&srv, calls).await;
+        assert_eq!(results.len(), 2);
+    }
+}
@@ -3,17 +3,35 @@
 //! Implements the MCP 2025-03-26 specification with Streamable HTTP transport.
 //! Configure with tools/resources JSON, register handlers, and serve via Axum.
 
+pub mod auth;
+pub mod batch;
 pub mod loader;
+pub mod notification;
+pub mod rpc_error;
 pub mod server;
+pub mod sessions;
+pub mod subscriptions;
 pub mod transport_http;
+pub mod transport_stdio;
+pub mod transport_ws;
 pub mod types;
 mod validate;
+pub mod ws_client;
 
 // Re-export the most commonly used items at the crate root.
+pub use auth::{with_bearer_auth, AuthConfig, AuthContext, StaticTokenValidator, TokenValidator};
+pub use batch::{run_batch, BatchCall, BatchCallResult, OnError};
 pub use loader::{load_resources, load_tools, parse_resources, parse_tools};
+pub use notification::{Notification, NotificationRegistry};
+pub use rpc_error::RpcErrorKind;
 pub use server::{FnToolHandler, ResourceHandler, Server, ServerBuilder, ToolHandler};
+pub use sessions::{InMemorySessionStore, SessionStore};
+pub use subscriptions::{SubId, SubscriptionRegistry};
 pub use transport_http::http_router;
+pub use transport_stdio::run_stdio;
+pub use transport_ws::ws_router;
 pub use types::{
     error_result, new_error_response, text_result, ContentBlock, JsonRpcRequest, JsonRpcResponse,
     McpError, Resource, ResourceContent, RpcError, Tool, ToolResult, PROTOCOL_VERSION,
 };
+pub use ws_client::WsClient;
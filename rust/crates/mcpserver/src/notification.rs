@@ -0,0 +1,266 @@
+//! Numeric-id push subscriptions, modelled on ethers-rs's `eth_subscribe`:
+//! a caller subscribes once and gets back an opaque numeric id as the
+//! `result`; the server then pushes [`Notification`] frames tagged with
+//! that id to whichever sink is registered for it, entirely independent of
+//! the request/response path.
+//!
+//! This is a different shape from [`crate::subscriptions::SubscriptionRegistry`],
+//! which multiplexes a single `GET /mcp/events` SSE connection across
+//! `*/subscribe` methods keyed by connection id. Here, one owner (e.g. a
+//! user id) can hold many independently-unsubscribable subscriptions, each
+//! numbered and each with its own outbound sink — the right shape for
+//! per-resource feeds like a channel's messages.
+//!
+//! Each subscription also keeps a bounded ring buffer of its recent
+//! frames, tagged with a monotonically increasing `event_id`. Borrowed
+//! from ethers-rs's reconnection/reissuance handling: a flaky streaming
+//! transport that drops and reconnects calls [`NotificationRegistry::resume`]
+//! with the last `event_id` its client saw, gets back everything it
+//! missed, and keeps the same subscription id rather than forcing the
+//! caller to re-subscribe from scratch.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use dashmap::DashMap;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::mpsc;
+
+/// Backlog of a subscriber's frame channel before a slow or stalled
+/// receiver starts dropping notifications rather than blocking the
+/// publisher.
+const NOTIFICATION_CHANNEL_CAPACITY: usize = 64;
+
+/// Recent frames kept per subscription for replay on reconnect. Bounds how
+/// far back a long-offline client can be resumed, so a dead connection
+/// can't make this grow unboundedly.
+const REPLAY_BUFFER_CAPACITY: usize = 256;
+
+/// A server-initiated JSON-RPC 2.0 notification frame. Carries no `id`, so
+/// it never expects a response — this is what flows down a subscription
+/// independently of [`crate::types::JsonRpcResponse`].
+///
+/// `event_id` is monotonically increasing per subscription and is the
+/// resume cursor a reconnecting client echoes back via
+/// [`NotificationRegistry::resume`].
+#[derive(Debug, Clone, Serialize)]
+pub struct Notification {
+    pub jsonrpc: &'static str,
+    pub method: String,
+    pub params: Value,
+    pub event_id: u64,
+}
+
+impl Notification {
+    fn new(method: impl Into<String>, params: Value, event_id: u64) -> Self {
+        Notification {
+            jsonrpc: "2.0",
+            method: method.into(),
+            params,
+            event_id,
+        }
+    }
+}
+
+/// One subscription's live sink plus its replay state.
+struct Subscription {
+    sender: mpsc::Sender<Notification>,
+    next_event_id: AtomicU64,
+    /// Recent frames, oldest first, capped at [`REPLAY_BUFFER_CAPACITY`].
+    recent: Mutex<VecDeque<Notification>>,
+}
+
+impl Subscription {
+    fn new(sender: mpsc::Sender<Notification>) -> Self {
+        Subscription {
+            sender,
+            next_event_id: AtomicU64::new(0),
+            recent: Mutex::new(VecDeque::new()),
+        }
+    }
+}
+
+/// Allocates numeric subscription ids per owner and holds the outbound
+/// sink each one delivers to, so a publisher can push a [`Notification`]
+/// to one specific `(owner, subscription id)` pair without knowing
+/// anything about the transport underneath.
+#[derive(Default)]
+pub struct NotificationRegistry {
+    next_id: AtomicU64,
+    subscriptions: DashMap<(String, u64), Subscription>,
+}
+
+impl NotificationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allocate a new subscription id for `owner` and register its sink.
+    /// Returns the id (the `result` of the subscribe call) and the
+    /// receiving half for whatever drains it (a transport loop, or a
+    /// polling tool call).
+    pub fn subscribe(&self, owner: &str) -> (u64, mpsc::Receiver<Notification>) {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = mpsc::channel(NOTIFICATION_CHANNEL_CAPACITY);
+        self.subscriptions
+            .insert((owner.to_string(), id), Subscription::new(tx));
+        (id, rx)
+    }
+
+    /// Re-attach a fresh sink to an existing `(owner, subscription_id)`
+    /// subscription after a streaming transport reconnects, replaying
+    /// every buffered frame with `event_id > since_event_id` instead of
+    /// making the caller re-subscribe. Returns `None` if that subscription
+    /// isn't tracked here any more (e.g. the process restarted, or it was
+    /// unsubscribed) — the caller should fall back to
+    /// [`NotificationRegistry::subscribe`] and re-materialize it fresh.
+    ///
+    /// Dedupes against double-registration: reconnecting replaces this
+    /// subscription's sink in place rather than allocating a new entry, so
+    /// a still-live subscription is never tracked twice.
+    pub fn resume(
+        &self,
+        owner: &str,
+        subscription_id: u64,
+        since_event_id: u64,
+    ) -> Option<(mpsc::Receiver<Notification>, Vec<Notification>)> {
+        let (tx, rx) = mpsc::channel(NOTIFICATION_CHANNEL_CAPACITY);
+        let mut entry = self.subscriptions.get_mut(&(owner.to_string(), subscription_id))?;
+        entry.sender = tx;
+        let replay = entry
+            .recent
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|n| n.event_id > since_event_id)
+            .cloned()
+            .collect();
+
+        Some((rx, replay))
+    }
+
+    /// Drop `owner`'s `subscription_id` sink. Safe to call more than once
+    /// or with an id that was never registered (already unsubscribed, or
+    /// owned by a different node) — both are a no-op.
+    pub fn unsubscribe(&self, owner: &str, subscription_id: u64) {
+        self.subscriptions.remove(&(owner.to_string(), subscription_id));
+    }
+
+    /// Drop every subscription belonging to `owner`, e.g. on disconnect, so
+    /// the fan-out task doesn't keep sending into a dead connection.
+    pub fn unsubscribe_all(&self, owner: &str) {
+        self.subscriptions.retain(|(o, _), _| o != owner);
+    }
+
+    /// Push `method`/`result` to `owner`'s `subscription_id` sink, tagging
+    /// the frame with that id the way an `eth_subscription` notification
+    /// carries its subscription id in `params.subscription`, and with the
+    /// next `event_id` in that subscription's sequence. The frame is
+    /// always buffered for replay (even if the live sink is currently
+    /// down) before delivery is attempted. A full (lagging) or closed
+    /// (disconnected) receiver just means nothing was delivered live — the
+    /// buffered copy is still there for the next [`resume`](Self::resume).
+    /// A no-op if that `(owner, id)` pair isn't registered here at all.
+    pub async fn notify(&self, owner: &str, subscription_id: u64, method: &str, result: Value) {
+        let Some(sub) = self.subscriptions.get(&(owner.to_string(), subscription_id)) else {
+            return;
+        };
+
+        let event_id = sub.next_event_id.fetch_add(1, Ordering::SeqCst);
+        let params = serde_json::json!({"subscription": subscription_id, "result": result});
+        let frame = Notification::new(method, params, event_id);
+
+        {
+            let mut recent = sub.recent.lock().unwrap();
+            recent.push_back(frame.clone());
+            while recent.len() > REPLAY_BUFFER_CAPACITY {
+                recent.pop_front();
+            }
+        }
+
+        let _ = sub.sender.try_send(frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscribe_allocates_unique_ids() {
+        let reg = NotificationRegistry::new();
+        let (id1, _rx1) = reg.subscribe("user-1");
+        let (id2, _rx2) = reg.subscribe("user-1");
+        assert_ne!(id1, id2);
+    }
+
+    #[tokio::test]
+    async fn test_notify_delivers_tagged_frame_with_event_id() {
+        let reg = NotificationRegistry::new();
+        let (id, mut rx) = reg.subscribe("user-1");
+        reg.notify("user-1", id, "notifications/channel", serde_json::json!({"hello": "world"}))
+            .await;
+        let frame = rx.recv().await.unwrap();
+        assert_eq!(frame.method, "notifications/channel");
+        assert_eq!(frame.params["subscription"], id);
+        assert_eq!(frame.params["result"]["hello"], "world");
+        assert_eq!(frame.event_id, 0);
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_stops_delivery() {
+        let reg = NotificationRegistry::new();
+        let (id, mut rx) = reg.subscribe("user-1");
+        reg.unsubscribe("user-1", id);
+        reg.notify("user-1", id, "notifications/channel", serde_json::json!({})).await;
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_all_drops_every_subscription() {
+        let reg = NotificationRegistry::new();
+        let (id1, mut rx1) = reg.subscribe("user-1");
+        let (id2, mut rx2) = reg.subscribe("user-1");
+        reg.unsubscribe_all("user-1");
+        reg.notify("user-1", id1, "m", serde_json::json!({})).await;
+        reg.notify("user-1", id2, "m", serde_json::json!({})).await;
+        assert!(rx1.try_recv().is_err());
+        assert!(rx2.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_resume_replays_missed_frames_and_keeps_id() {
+        let reg = NotificationRegistry::new();
+        let (id, rx1) = reg.subscribe("user-1");
+        reg.notify("user-1", id, "m", serde_json::json!(1)).await;
+        reg.notify("user-1", id, "m", serde_json::json!(2)).await;
+        drop(rx1); // simulate the connection dropping
+
+        let (mut rx2, replay) = reg.resume("user-1", id, 0).unwrap();
+        assert_eq!(replay.len(), 1);
+        assert_eq!(replay[0].params["result"], 2);
+
+        reg.notify("user-1", id, "m", serde_json::json!(3)).await;
+        let frame = rx2.recv().await.unwrap();
+        assert_eq!(frame.params["result"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_resume_unknown_subscription_returns_none() {
+        let reg = NotificationRegistry::new();
+        assert!(reg.resume("user-1", 999, 0).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_replay_buffer_is_bounded() {
+        let reg = NotificationRegistry::new();
+        let (id, _rx) = reg.subscribe("user-1");
+        for i in 0..(REPLAY_BUFFER_CAPACITY + 10) {
+            reg.notify("user-1", id, "m", serde_json::json!(i)).await;
+        }
+        let (_rx2, replay) = reg.resume("user-1", id, 0).unwrap();
+        assert_eq!(replay.len(), REPLAY_BUFFER_CAPACITY);
+    }
+}
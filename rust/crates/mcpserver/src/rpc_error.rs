@@ -0,0 +1,73 @@
+//! Structured taxonomy for JSON-RPC 2.0 protocol-level errors (malformed
+//! request, unknown method, bad params, ...) — distinct from
+//! [`crate::types::McpError`], which covers tool *handler* failures.
+//!
+//! Every place that used to hard-code a raw JSON-RPC error integer
+//! (`handle_jsonrpc`, `handle_jsonrpc_batch`, the `tools/callBatch`
+//! transport branch) now builds one of these and converts it, so the
+//! reserved code ranges from the spec are defined once instead of copied
+//! by hand at each call site.
+
+use serde_json::Value;
+
+use crate::types::{new_error_response, JsonRpcResponse};
+
+/// A JSON-RPC 2.0 protocol-level error, carrying its reserved code and an
+/// optional structured `data` payload (e.g. the offending param name).
+#[derive(Debug, Clone)]
+pub enum RpcErrorKind {
+    /// Invalid JSON was received by the server.
+    ParseError(String),
+    /// The JSON sent is not a valid request object.
+    InvalidRequest(String),
+    /// The method does not exist / is not available.
+    MethodNotFound(String),
+    /// Invalid method parameter(s); `data` carries details like the
+    /// offending field name when known.
+    InvalidParams { message: String, data: Option<Value> },
+    /// Anything that doesn't fit the other variants, with its own code.
+    Internal(i64, String),
+}
+
+impl RpcErrorKind {
+    pub fn code(&self) -> i64 {
+        match self {
+            RpcErrorKind::ParseError(_) => -32700,
+            RpcErrorKind::InvalidRequest(_) => -32600,
+            RpcErrorKind::MethodNotFound(_) => -32601,
+            RpcErrorKind::InvalidParams { .. } => -32602,
+            RpcErrorKind::Internal(code, _) => *code,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            RpcErrorKind::ParseError(msg)
+            | RpcErrorKind::InvalidRequest(msg)
+            | RpcErrorKind::MethodNotFound(msg)
+            | RpcErrorKind::Internal(_, msg) => msg.clone(),
+            RpcErrorKind::InvalidParams { message, .. } => message.clone(),
+        }
+    }
+
+    fn data(&self) -> Option<Value> {
+        match self {
+            RpcErrorKind::InvalidParams { data, .. } => data.clone(),
+            _ => None,
+        }
+    }
+
+    /// Build the wire [`JsonRpcResponse`] for this error, flowing through
+    /// the existing [`new_error_response`] mapping so callers get the same
+    /// envelope shape they always have.
+    pub fn into_response(self, id: Option<Value>) -> JsonRpcResponse {
+        let data = self.data();
+        let mut resp = new_error_response(id, self.code() as i32, self.message());
+        if let Some(data) = data {
+            if let Some(error) = resp.error.as_mut() {
+                error.data = Some(data);
+            }
+        }
+        resp
+    }
+}
@@ -0,0 +1,134 @@
+//! Pluggable `mcp-session-id` tracking for the HTTP transport: creation,
+//! liveness validation, and idle-timeout expiry, behind a trait so a
+//! deployment can swap the default in-memory store for one backed by its
+//! own storage (e.g. a DynamoDB table, reusing the same `put_item`/
+//! `query`/`delete_item` primitives other tools already use) without
+//! touching [`crate::transport_http`] itself.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+/// Default idle timeout before a session is treated as expired, when a
+/// deployment doesn't configure its own.
+pub const DEFAULT_SESSION_TTL_SECS: u64 = 30 * 60;
+
+/// Tracks `mcp-session-id` lifecycle for the HTTP transport.
+#[async_trait]
+pub trait SessionStore: Send + Sync {
+    /// Create and record a new session, returning its id.
+    async fn create(&self) -> String;
+    /// True if `session_id` exists and hasn't gone idle past its TTL.
+    async fn validate(&self, session_id: &str) -> bool;
+    /// Refresh a session's last-seen time, extending its TTL. A no-op if
+    /// the session doesn't exist (e.g. it already expired).
+    async fn touch(&self, session_id: &str);
+    /// Drop a session immediately (e.g. an explicit client-initiated
+    /// close, rather than waiting out the idle timeout).
+    async fn remove(&self, session_id: &str);
+}
+
+/// Default in-memory [`SessionStore`]: a map of session id to last-seen
+/// [`Instant`], swept for expired entries on each `create` rather than run
+/// off a background task, so the map never grows unbounded across a
+/// long-running process without needing its own eviction loop.
+pub struct InMemorySessionStore {
+    ttl: Duration,
+    sessions: RwLock<HashMap<String, Instant>>,
+}
+
+impl InMemorySessionStore {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            sessions: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn sweep(&self, sessions: &mut HashMap<String, Instant>) {
+        let ttl = self.ttl;
+        sessions.retain(|_, last_seen| last_seen.elapsed() < ttl);
+    }
+}
+
+impl Default for InMemorySessionStore {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(DEFAULT_SESSION_TTL_SECS))
+    }
+}
+
+#[async_trait]
+impl SessionStore for InMemorySessionStore {
+    async fn create(&self) -> String {
+        let id = Uuid::new_v4().to_string();
+        let mut sessions = self.sessions.write().unwrap();
+        self.sweep(&mut sessions);
+        sessions.insert(id.clone(), Instant::now());
+        id
+    }
+
+    async fn validate(&self, session_id: &str) -> bool {
+        let sessions = self.sessions.read().unwrap();
+        sessions
+            .get(session_id)
+            .is_some_and(|last_seen| last_seen.elapsed() < self.ttl)
+    }
+
+    async fn touch(&self, session_id: &str) {
+        let mut sessions = self.sessions.write().unwrap();
+        if let Some(last_seen) = sessions.get_mut(session_id) {
+            *last_seen = Instant::now();
+        }
+    }
+
+    async fn remove(&self, session_id: &str) {
+        self.sessions.write().unwrap().remove(session_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_create_then_validate() {
+        let store = InMemorySessionStore::default();
+        let id = store.create().await;
+        assert!(store.validate(&id).await);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_session_is_invalid() {
+        let store = InMemorySessionStore::default();
+        assert!(!store.validate("does-not-exist").await);
+    }
+
+    #[tokio::test]
+    async fn test_expired_session_is_invalid() {
+        let store = InMemorySessionStore::new(Duration::from_millis(1));
+        let id = store.create().await;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!store.validate(&id).await);
+    }
+
+    #[tokio::test]
+    async fn test_touch_extends_ttl() {
+        let store = InMemorySessionStore::new(Duration::from_millis(50));
+        let id = store.create().await;
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        store.touch(&id).await;
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(store.validate(&id).await);
+    }
+
+    #[tokio::test]
+    async fn test_remove_invalidates_session() {
+        let store = InMemorySessionStore::default();
+        let id = store.create().await;
+        store.remove(&id).await;
+        assert!(!store.validate(&id).await);
+    }
+}
@@ -0,0 +1,74 @@
+//! Server-initiated push over the Streamable HTTP transport.
+//!
+//! A client opens `GET /mcp/events` and holds the connection open as SSE;
+//! the server immediately announces a connection id as the first frame.
+//! The client then issues an ordinary `POST /mcp` call to a `*/subscribe`
+//! method whose JSON-RPC `id` equals that connection id, which claims the
+//! waiting sink. From then on, anything passed to [`SubscriptionRegistry::notify`]
+//! for that id is wrapped as a JSON-RPC notification object and written to
+//! the stream as an SSE `data:` frame, until a matching `*/unsubscribe`
+//! call (or the client disconnecting) drops the sink.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+/// Id correlating a `GET /mcp/events` connection with the `POST /mcp`
+/// `*/subscribe` call that claims it — the JSON-RPC request id of that call.
+pub type SubId = String;
+
+#[derive(Default)]
+pub struct SubscriptionRegistry {
+    /// Sinks for SSE connections that have announced themselves but
+    /// haven't yet been claimed by a `*/subscribe` call.
+    pending: Mutex<HashMap<SubId, mpsc::Sender<String>>>,
+    /// Sinks claimed by a `*/subscribe` call; live targets for [`notify`](Self::notify).
+    sinks: Mutex<HashMap<SubId, mpsc::Sender<String>>>,
+}
+
+impl SubscriptionRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that a new SSE connection is open under `conn_id`, waiting to
+    /// be claimed by a `*/subscribe` call.
+    pub fn announce(&self, conn_id: SubId, sink: mpsc::Sender<String>) {
+        self.pending.lock().unwrap().insert(conn_id, sink);
+    }
+
+    /// Claim the pending connection for `sub_id`, moving it to the live
+    /// sinks. Returns `false` if no connection is waiting under that id
+    /// (e.g. the `GET /mcp/events` request never happened, or already
+    /// expired).
+    pub fn subscribe(&self, sub_id: &str) -> bool {
+        let Some(sink) = self.pending.lock().unwrap().remove(sub_id) else {
+            return false;
+        };
+        self.sinks.lock().unwrap().insert(sub_id.to_string(), sink);
+        true
+    }
+
+    /// Drop the sink for `sub_id`, whether pending or live. Dropping the
+    /// sender ends the paired SSE stream, so this is also how the
+    /// connection itself is torn down. Safe to call more than once.
+    pub fn unsubscribe(&self, sub_id: &str) {
+        self.sinks.lock().unwrap().remove(sub_id);
+        self.pending.lock().unwrap().remove(sub_id);
+    }
+
+    /// Send `method`/`params` as a JSON-RPC notification to `sub_id`'s live
+    /// sink, if it's still subscribed. A closed receiver (client
+    /// disconnected without unsubscribing) just evicts the stale sink.
+    pub async fn notify(&self, sub_id: &str, method: &str, params: Value) {
+        let sink = self.sinks.lock().unwrap().get(sub_id).cloned();
+        let Some(sink) = sink else { return };
+
+        let frame = json!({"jsonrpc": "2.0", "method": method, "params": params}).to_string();
+        if sink.send(frame).await.is_err() {
+            self.sinks.lock().unwrap().remove(sub_id);
+        }
+    }
+}
@@ -0,0 +1,729 @@
+//! HTTP transport implementing the MCP "Streamable HTTP" mode: `POST /mcp`
+//! for ordinary request/response, optionally streamed back as a one-frame
+//! SSE response when the caller sends `Accept: text/event-stream`, plus
+//! `GET /mcp` for a session's long-lived server-to-client event stream.
+//!
+//! Incremental *multi-frame* tool results (a `ToolHandler::call` emitting a
+//! sequence of `text_result` chunks as it runs, rather than one frame at
+//! the end) would need [`Server::handle`] itself to grow a streaming
+//! variant yielding a `Stream` of responses instead of a single value —
+//! out of scope here since it touches every registered handler's
+//! signature, not just this transport. What's here covers the transport
+//! half: both endpoints, and the per-session plumbing a streaming
+//! `Server::handle` would hang its frames off of.
+
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    extract::{Extension, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use futures::stream::{self, Stream, StreamExt};
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use uuid::Uuid;
+
+use crate::auth::AuthContext;
+use crate::batch::{run_batch, BatchCall};
+use crate::rpc_error::RpcErrorKind;
+use crate::server::Server;
+use crate::sessions::{InMemorySessionStore, SessionStore};
+use crate::subscriptions::SubscriptionRegistry;
+use crate::types::JsonRpcRequest;
+
+/// Backlog of a subscriber's SSE frame channel before a slow client starts
+/// missing notifications.
+const SSE_CHANNEL_CAPACITY: usize = 64;
+
+/// How often a keep-alive comment frame is sent on an open `/mcp/events`
+/// stream so intermediaries (proxies, load balancers) don't time it out.
+const SSE_KEEP_ALIVE_SECS: u64 = 15;
+
+/// Shared state for the HTTP handler.
+pub(crate) struct HttpState {
+    server: Server,
+    sessions: Arc<dyn SessionStore>,
+    subscriptions: SubscriptionRegistry,
+}
+
+/// Create an Axum router for the MCP server, tracking `mcp-session-id`s in
+/// an [`InMemorySessionStore`] with the default idle timeout. Use
+/// [`http_router_with_sessions`] to plug in a different [`SessionStore`]
+/// (e.g. one backed by a shared DynamoDB table, for a deployment running
+/// more than one instance behind a load balancer).
+pub fn http_router(server: Server) -> Router {
+    http_router_with_sessions(server, Arc::new(InMemorySessionStore::default()))
+}
+
+/// Create an Axum router for the MCP server with a caller-supplied
+/// [`SessionStore`].
+pub fn http_router_with_sessions(server: Server, sessions: Arc<dyn SessionStore>) -> Router {
+    let state = Arc::new(HttpState {
+        server,
+        sessions,
+        subscriptions: SubscriptionRegistry::new(),
+    });
+
+    Router::new()
+        .route("/mcp", post(handle_mcp).get(handle_mcp_get))
+        .route("/mcp/events", get(handle_mcp_events))
+        .route("/healthz", get(handle_healthz))
+        .with_state(state)
+}
+
+/// True when `headers` names `text/event-stream` in its `Accept` list —
+/// the Streamable HTTP signal that the caller wants an SSE stream rather
+/// than a plain JSON body, on either `GET /mcp` (open the session's event
+/// stream) or `POST /mcp` (stream this call's own response back instead of
+/// a single JSON object).
+fn wants_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(axum::http::header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("text/event-stream"))
+}
+
+/// `GET /mcp` with `Accept: text/event-stream` — the Streamable HTTP
+/// transport's server-to-client leg: an SSE stream of `notifications/*`
+/// and response frames for the session named by the `mcp-session-id`
+/// header, opened once and kept alive for the life of the session.
+///
+/// Without that `Accept` header this falls through to a plain 405, same as
+/// before this existed — `GET /mcp` was never a thing clients could do
+/// otherwise, so nothing that wasn't already broken regresses.
+async fn handle_mcp_get(State(state): State<Arc<HttpState>>, headers: HeaderMap) -> Response {
+    if !wants_event_stream(&headers) {
+        return StatusCode::METHOD_NOT_ALLOWED.into_response();
+    }
+
+    let Some(session_id) = headers.get("mcp-session-id").and_then(|v| v.to_str().ok()).map(str::to_string) else {
+        return (StatusCode::BAD_REQUEST, "mcp-session-id header required to open an event stream").into_response();
+    };
+    if !state.sessions.validate(&session_id).await {
+        return (StatusCode::NOT_FOUND, "unknown or expired mcp-session-id").into_response();
+    }
+    state.sessions.touch(&session_id).await;
+
+    // Keyed by session id (rather than an ad-hoc announced connection id
+    // like `/mcp/events`) so a `POST /mcp` on the same session can deliver
+    // results out-of-band on this stream without a separate
+    // announce/subscribe round trip first.
+    let (tx, rx) = mpsc::channel::<String>(SSE_CHANNEL_CAPACITY);
+    state.subscriptions.announce(session_id.clone(), tx);
+    state.subscriptions.subscribe(&session_id);
+
+    let frames = ReceiverStream::new(rx).map(|data| Ok::<_, Infallible>(Event::default().data(data)));
+    Sse::new(frames)
+        .keep_alive(
+            KeepAlive::new()
+                .interval(Duration::from_secs(SSE_KEEP_ALIVE_SECS))
+                .text("keep-alive"),
+        )
+        .into_response()
+}
+
+async fn handle_healthz() -> impl IntoResponse {
+    Json(json!({"status": "ok"}))
+}
+
+/// `GET /mcp/events` — hold an SSE connection open for server-initiated
+/// notifications. The first frame announces the connection id the client
+/// must echo back as the `id` of a later `POST /mcp` call to a
+/// `*/subscribe` method to start receiving anything.
+async fn handle_mcp_events(State(state): State<Arc<HttpState>>) -> impl IntoResponse {
+    let conn_id = Uuid::new_v4().to_string();
+    let (tx, rx) = mpsc::channel::<String>(SSE_CHANNEL_CAPACITY);
+    state.subscriptions.announce(conn_id.clone(), tx);
+
+    let announce = stream::once(async move {
+        Ok::<_, Infallible>(
+            Event::default()
+                .event("connected")
+                .data(json!({"id": conn_id}).to_string()),
+        )
+    });
+    let frames = ReceiverStream::new(rx).map(|data| Ok::<_, Infallible>(Event::default().data(data)));
+
+    Sse::new(announce.chain(frames)).keep_alive(
+        KeepAlive::new()
+            .interval(Duration::from_secs(SSE_KEEP_ALIVE_SECS))
+            .text("keep-alive"),
+    )
+}
+
+/// Identify the string key a `*/subscribe` or `*/unsubscribe` call's id
+/// resolves to — matching the connection id announced by `GET /mcp/events`.
+fn id_key(id: &Option<Value>) -> String {
+    match id {
+        Some(Value::String(s)) => s.clone(),
+        Some(v) => v.to_string(),
+        None => String::new(),
+    }
+}
+
+async fn handle_mcp(
+    State(state): State<Arc<HttpState>>,
+    headers: HeaderMap,
+    auth: Option<Extension<AuthContext>>,
+    Json(body): Json<Value>,
+) -> Response {
+    if body.is_array() {
+        return handle_mcp_batch(state, body).await;
+    }
+
+    let req: JsonRpcRequest = match serde_json::from_value(body) {
+        Ok(req) => req,
+        Err(e) => {
+            let err = RpcErrorKind::ParseError(e.to_string());
+            return (StatusCode::BAD_REQUEST, Json(err.into_response(None))).into_response();
+        }
+    };
+
+    handle_mcp_single(state, headers, auth.map(|Extension(ctx)| ctx), req).await
+}
+
+/// A JSON-RPC 2.0 batch: the body was a top-level array rather than a
+/// single request object. Per spec, each member is dispatched through the
+/// ordinary [`Server::handle`] path — concurrently via
+/// [`futures::future::join_all`], unlike [`run_batch`], which runs its
+/// calls one after another — and notifications (no `id`) are executed but produce no
+/// array element. An empty array is itself invalid per spec and gets a
+/// single `-32600` error rather than an empty array back.
+///
+/// Unlike a single `POST /mcp` call, batch members don't get the
+/// `initialize` session-id dance or `*/subscribe` pairing — those are
+/// transport-session concepts that don't make sense to multiplex inside
+/// one batch, so each member here goes straight to `Server::handle`.
+async fn handle_mcp_batch(state: Arc<HttpState>, body: Value) -> Response {
+    let reqs: Vec<JsonRpcRequest> = match serde_json::from_value(body) {
+        Ok(reqs) => reqs,
+        Err(e) => {
+            let err = RpcErrorKind::ParseError(e.to_string());
+            return (StatusCode::BAD_REQUEST, Json(err.into_response(None))).into_response();
+        }
+    };
+
+    match dispatch_batch(&state.server, reqs).await {
+        // Every member was a notification: nothing to send back, same as a
+        // lone notification on the non-batch path.
+        Ok(responses) if responses.is_empty() => (StatusCode::ACCEPTED, Body::empty()).into_response(),
+        Ok(responses) => Json(responses).into_response(),
+        Err(err) => (StatusCode::BAD_REQUEST, Json(err)).into_response(),
+    }
+}
+
+/// Dispatch a parsed JSON-RPC 2.0 batch concurrently via
+/// [`futures::future::join_all`] (unlike [`run_batch`], which is
+/// sequential), filtering out notifications (no `id`) per spec. An empty
+/// batch is itself invalid and comes back as `Err` holding the single
+/// `-32600` error object rather than an empty `Ok(vec![])` — callers
+/// serialize whichever side they get directly onto the wire.
+///
+/// Called directly by `transport_http`'s `POST /mcp` and, via
+/// [`dispatch_any`], by `transport_ws` and `transport_stdio`, so the batch
+/// semantics are defined once regardless of framing.
+pub(crate) async fn dispatch_batch(server: &Server, reqs: Vec<JsonRpcRequest>) -> Result<Vec<Value>, Value> {
+    if reqs.is_empty() {
+        let err = RpcErrorKind::InvalidRequest("batch must not be empty".into());
+        return Err(serde_json::to_value(err.into_response(None)).unwrap());
+    }
+
+    let calls = reqs.into_iter().map(|req| server.handle(req));
+
+    let responses = futures::future::join_all(calls)
+        .await
+        .into_iter()
+        .filter(|resp| !resp.is_notification())
+        .map(|resp| serde_json::to_value(&resp).unwrap())
+        .collect();
+
+    Ok(responses)
+}
+
+/// Parse a raw JSON-RPC message — a single request object or a top-level
+/// batch array — and dispatch it through `server`. The single shared
+/// entry point behind `transport_ws` and `transport_stdio`, which only
+/// differ in framing (a WebSocket text frame vs. a stdout line); unlike
+/// `transport_http`'s `POST /mcp`, neither gets the `initialize`
+/// session-id dance since both are already one persistent connection per
+/// client.
+///
+/// Returns `None` when nothing should be written back (a lone
+/// notification), `Some` otherwise — a single response object, a batch
+/// array, or a protocol-level error object.
+pub(crate) async fn dispatch_any(server: &Server, raw: &str) -> Option<Value> {
+    let value: Value = match serde_json::from_str(raw) {
+        Ok(v) => v,
+        Err(e) => return Some(serde_json::to_value(RpcErrorKind::ParseError(e.to_string()).into_response(None)).unwrap()),
+    };
+
+    if value.is_array() {
+        return match serde_json::from_value::<Vec<JsonRpcRequest>>(value) {
+            Ok(reqs) => match dispatch_batch(server, reqs).await {
+                Ok(responses) => Some(Value::Array(responses)),
+                Err(err) => Some(err),
+            },
+            Err(e) => Some(serde_json::to_value(RpcErrorKind::ParseError(e.to_string()).into_response(None)).unwrap()),
+        };
+    }
+
+    let req: JsonRpcRequest = match serde_json::from_value(value) {
+        Ok(req) => req,
+        Err(e) => return Some(serde_json::to_value(RpcErrorKind::ParseError(e.to_string()).into_response(None)).unwrap()),
+    };
+
+    let resp = server.handle(req).await;
+    if resp.is_notification() {
+        None
+    } else {
+        Some(serde_json::to_value(&resp).unwrap())
+    }
+}
+
+/// Send `body` back as the response to a `POST /mcp` call: a plain JSON
+/// object normally, or — when the caller sent `Accept: text/event-stream`
+/// — that same object as the lone `message` event of a short-lived SSE
+/// stream that closes right after, the Streamable HTTP option for a client
+/// that wants every response framed the same way regardless of whether
+/// the call ends up needing more than one frame. `None` means a
+/// notification: no body either way, just 202 (JSON) or an immediately
+/// closed empty stream (SSE).
+fn finish_response(accepts_sse: bool, session_id: Option<&str>, body: Option<Value>) -> Response {
+    let mut response = if accepts_sse {
+        let frame = body.map(|body| Event::default().event("message").data(body.to_string()));
+        let stream = stream::iter(frame).map(Ok::<_, Infallible>);
+        Sse::new(stream)
+            .keep_alive(KeepAlive::new().interval(Duration::from_secs(SSE_KEEP_ALIVE_SECS)).text("keep-alive"))
+            .into_response()
+    } else {
+        match body {
+            Some(body) => Json(body).into_response(),
+            None => (StatusCode::ACCEPTED, Body::empty()).into_response(),
+        }
+    };
+
+    if let Some(sid) = session_id {
+        response.headers_mut().insert("mcp-session-id", sid.parse().unwrap());
+    }
+    response
+}
+
+async fn handle_mcp_single(
+    state: Arc<HttpState>,
+    headers: HeaderMap,
+    auth: Option<AuthContext>,
+    mut req: JsonRpcRequest,
+) -> Response {
+    let accepts_sse = wants_event_stream(&headers);
+
+    // [`crate::auth::with_bearer_auth`] (when the deployment turns it on)
+    // has already verified the caller by this point and stashed the
+    // identity in request extensions — splice it into `params.arguments`
+    // as `_auth` so a tool handler's `args` (what it actually receives)
+    // carries an already-verified identity instead of it needing to
+    // re-derive one from a token the caller put in `args` itself. This is
+    // the only channel from this transport down to a handler until
+    // `Server::handle` grows a proper context parameter.
+    if let Some(ctx) = auth {
+        if let Some(Value::Object(params)) = req.params.as_mut() {
+            if let Some(Value::Object(arguments)) = params.get_mut("arguments") {
+                arguments.insert("_auth".into(), json!({"subject": ctx.subject, "scopes": ctx.scopes}));
+            }
+        }
+    }
+
+    // Session management: create on initialize, validate (and refresh the
+    // idle timeout on) every other call that names one. A client that
+    // never sent `initialize` first is still allowed through statelessly —
+    // only a *named but invalid* session is rejected.
+    let session_id = if req.method == "initialize" {
+        Some(state.sessions.create().await)
+    } else if let Some(hdr) = headers.get("mcp-session-id") {
+        let id = hdr.to_str().unwrap_or_default().to_string();
+        if !state.sessions.validate(&id).await {
+            let err = RpcErrorKind::InvalidRequest("unknown or expired mcp-session-id".into());
+            return (StatusCode::BAD_REQUEST, Json(err.into_response(req.id))).into_response();
+        }
+        state.sessions.touch(&id).await;
+        Some(id)
+    } else {
+        None
+    };
+
+    // `tools/callBatch` isn't a method `Server::handle` dispatches — each
+    // call in it runs through the ordinary `tools/call` path instead, so it
+    // lives here at the transport boundary rather than in the dispatcher.
+    if req.method == "tools/callBatch" {
+        let id = req.id.clone();
+        let calls: Vec<BatchCall> = match req
+            .params
+            .as_ref()
+            .and_then(|p| p.get("calls"))
+            .cloned()
+            .map(serde_json::from_value)
+        {
+            Some(Ok(calls)) => calls,
+            _ => {
+                let err = RpcErrorKind::InvalidParams {
+                    message: "params.calls must be an array of batch calls".into(),
+                    data: None,
+                };
+                return (StatusCode::BAD_REQUEST, Json(err.into_response(id))).into_response();
+            }
+        };
+
+        let results = run_batch(&state.server, calls).await;
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": {"results": results},
+        });
+        return finish_response(accepts_sse, session_id.as_deref(), Some(body));
+    }
+
+    // Pair with a `GET /mcp/events` connection announced under the same id,
+    // rather than dispatching through the ordinary tool/method path.
+    if req.method.ends_with("/subscribe") || req.method.ends_with("/unsubscribe") {
+        let key = id_key(&req.id);
+        let body = if req.method.ends_with("/subscribe") {
+            if state.subscriptions.subscribe(&key) {
+                json!({"jsonrpc": "2.0", "id": req.id, "result": {"subscribed": true}})
+            } else {
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": req.id,
+                    "error": {"code": -32001, "message": "no open GET /mcp/events connection for this id"},
+                })
+            }
+        } else {
+            state.subscriptions.unsubscribe(&key);
+            json!({"jsonrpc": "2.0", "id": req.id, "result": {"unsubscribed": true}})
+        };
+
+        return finish_response(accepts_sse, session_id.as_deref(), Some(body));
+    }
+
+    let resp = state.server.handle(req).await;
+    let body = (!resp.is_notification()).then(|| serde_json::to_value(&resp).unwrap());
+    finish_response(accepts_sse, session_id.as_deref(), body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::server::{FnToolHandler, Server};
+    use crate::types::text_result;
+    use axum::body::Body;
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    fn test_router() -> Router {
+        let mut srv = Server::builder()
+            .tools_json(
+                r#"[{"name":"echo","description":"test","inputSchema":{"type":"object","properties":{"msg":{"type":"string"}}}}]"#.as_bytes(),
+            )
+            .resources_json(r#"[]"#.as_bytes())
+            .server_info("test", "0.1")
+            .build();
+        srv.handle_tool("echo", FnToolHandler::new(|args: serde_json::Value| async move {
+            let msg = args.get("msg").and_then(|v| v.as_str()).unwrap_or("");
+            Ok(text_result(&json!({"msg": msg}).to_string()))
+        }));
+        http_router(srv)
+    }
+
+    fn json_body(body: serde_json::Value) -> Body {
+        Body::from(serde_json::to_vec(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_health_check() {
+        let app = test_router();
+        let req = Request::builder()
+            .method("GET")
+            .uri("/healthz")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_initialize_returns_session_id() {
+        let app = test_router();
+        let body = json!({
+            "jsonrpc": "2.0", "id": 1, "method": "initialize",
+            "params": {"protocolVersion": "2025-03-26", "capabilities": {}, "clientInfo": {"name": "test", "version": "0.1"}}
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .body(json_body(body))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(resp.headers().contains_key("mcp-session-id"));
+    }
+
+    #[tokio::test]
+    async fn test_bearer_auth_injects_verified_identity_into_tool_args() {
+        use crate::auth::{with_bearer_auth, AuthConfig, StaticTokenValidator};
+
+        let mut srv = Server::builder()
+            .tools_json(r#"[{"name":"whoami","description":"test","inputSchema":{"type":"object","properties":{}}}]"#.as_bytes())
+            .resources_json(r#"[]"#.as_bytes())
+            .server_info("test", "0.1")
+            .build();
+        srv.handle_tool("whoami", FnToolHandler::new(|args: serde_json::Value| async move {
+            let subject = args.get("_auth").and_then(|a| a.get("subject")).and_then(|s| s.as_str()).unwrap_or("");
+            Ok(text_result(subject))
+        }));
+
+        let validator = StaticTokenValidator::new().insert("good-token", "user-42", vec![]);
+        let config = Arc::new(AuthConfig::new(Arc::new(validator), "https://auth.example.com/.well-known/oauth-protected-resource"));
+        let app = with_bearer_auth(http_router(srv), config);
+
+        let body = json!({
+            "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+            "params": {"name": "whoami", "arguments": {}}
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .header("authorization", "Bearer good-token")
+            .body(json_body(body))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let text = parsed["result"]["content"][0]["text"].as_str().unwrap();
+        assert_eq!(text, "user-42");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_session_id_is_rejected_with_jsonrpc_error() {
+        let app = test_router();
+        let body = json!({
+            "jsonrpc": "2.0", "id": 1, "method": "tools/list",
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .header("mcp-session-id", "not-a-real-session")
+            .body(json_body(body))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(parsed["error"]["code"], -32600);
+    }
+
+    #[tokio::test]
+    async fn test_notification_returns_202() {
+        let app = test_router();
+        let body = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .body(json_body(body))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+    }
+
+    #[tokio::test]
+    async fn test_tools_list() {
+        let app = test_router();
+        let body = json!({"jsonrpc": "2.0", "id": 1, "method": "tools/list"});
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .body(json_body(body))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_jsonrpc_batch_returns_array_and_omits_notifications() {
+        let app = test_router();
+        let body = json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "ping"},
+            {"jsonrpc": "2.0", "method": "notifications/initialized"},
+            {"jsonrpc": "2.0", "id": 2, "method": "ping"},
+        ]);
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .body(json_body(body))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let results = parsed.as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["id"], 1);
+        assert_eq!(results[1]["id"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_jsonrpc_batch_all_notifications_returns_202() {
+        let app = test_router();
+        let body = json!([
+            {"jsonrpc": "2.0", "method": "notifications/initialized"},
+            {"jsonrpc": "2.0", "method": "notifications/cancelled"},
+        ]);
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .body(json_body(body))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::ACCEPTED);
+
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        assert!(bytes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_jsonrpc_batch_empty_array_is_invalid_request() {
+        let app = test_router();
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .body(json_body(json!([])))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(parsed["error"]["code"], -32600);
+    }
+
+    #[tokio::test]
+    async fn test_tools_call_batch() {
+        let app = test_router();
+        let body = json!({
+            "jsonrpc": "2.0", "id": 1, "method": "tools/callBatch",
+            "params": {"calls": [
+                {"id": "c1", "name": "echo", "arguments": {"msg": "hi"}},
+                {"id": "c2", "name": "echo", "arguments": {"msg": "#msg"}},
+            ]}
+        });
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .body(json_body(body))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let parsed: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        let results = parsed["result"]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["id"], "c1");
+        let echoed_back: serde_json::Value =
+            serde_json::from_str(results[1]["result"]["content"][0]["text"].as_str().unwrap()).unwrap();
+        assert_eq!(echoed_back["msg"], "hi");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_json() {
+        let app = test_router();
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .body(Body::from("{bad json"))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        // Axum returns 422 for malformed JSON by default
+        assert!(resp.status().is_client_error());
+    }
+
+    #[tokio::test]
+    async fn test_method_not_allowed() {
+        let app = test_router();
+        let req = Request::builder()
+            .method("GET")
+            .uri("/mcp")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[tokio::test]
+    async fn test_get_mcp_without_session_id_is_bad_request() {
+        let app = test_router();
+        let req = Request::builder()
+            .method("GET")
+            .uri("/mcp")
+            .header("accept", "text/event-stream")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_get_mcp_with_unknown_session_id_is_not_found() {
+        let app = test_router();
+        let req = Request::builder()
+            .method("GET")
+            .uri("/mcp")
+            .header("accept", "text/event-stream")
+            .header("mcp-session-id", "does-not-exist")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_post_mcp_with_sse_accept_streams_response() {
+        let app = test_router();
+        let body = json!({"jsonrpc": "2.0", "id": 1, "method": "ping"});
+        let req = Request::builder()
+            .method("POST")
+            .uri("/mcp")
+            .header("content-type", "application/json")
+            .header("accept", "text/event-stream")
+            .body(json_body(body))
+            .unwrap();
+        let resp = app.oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers().get("content-type").and_then(|v| v.to_str().ok()),
+            Some("text/event-stream")
+        );
+
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(bytes.to_vec()).unwrap();
+        assert!(text.contains("event: message"));
+        assert!(text.contains("\"id\":1"));
+    }
+}
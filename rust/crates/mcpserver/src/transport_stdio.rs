@@ -0,0 +1,41 @@
+//! stdio transport: the MCP "stdio" transport — newline-delimited
+//! JSON-RPC over stdin/stdout, for clients that spawn the server as a
+//! child process rather than talking HTTP or WebSocket.
+//!
+//! Each line is a single JSON-RPC request or a top-level batch array,
+//! dispatched through [`dispatch_any`] — the same parse/dispatch logic
+//! [`crate::transport_ws`] uses, so only the framing differs between
+//! transports.
+
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+use crate::server::Server;
+use crate::transport_http::dispatch_any;
+
+/// Run the stdio transport loop against `server`: read newline-delimited
+/// JSON-RPC messages from stdin, dispatch each, and write any response as
+/// its own line to stdout. Returns once stdin hits EOF.
+pub async fn run_stdio(server: Server) {
+    let stdin = tokio::io::stdin();
+    let mut stdout = tokio::io::stdout();
+    let mut lines = BufReader::new(stdin).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let Some(reply) = dispatch_any(&server, line).await else { continue };
+
+        if stdout.write_all(reply.to_string().as_bytes()).await.is_err() {
+            break;
+        }
+        if stdout.write_all(b"\n").await.is_err() {
+            break;
+        }
+        if stdout.flush().await.is_err() {
+            break;
+        }
+    }
+}
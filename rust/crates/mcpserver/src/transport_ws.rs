@@ -0,0 +1,80 @@
+//! WebSocket transport: a long-lived, bidirectional alternative to
+//! `transport_http`'s request/response `POST /mcp` for clients that want
+//! server-initiated pushes without polling — the same JSON-RPC-over-a-
+//! persistent-socket shape as electrum-client's `raw_client` or the
+//! WebSocket gateway in rvi_sota_client.
+//!
+//! Each inbound text frame is a single JSON-RPC request or a top-level
+//! batch array, dispatched through [`dispatch_any`] — the same
+//! parse/dispatch logic [`crate::transport_stdio`] uses, so only the
+//! framing differs between transports. Every connection also gets its own
+//! [`NotificationRegistry`] subscription, keyed by a fresh connection id,
+//! so [`crate::notification`] push frames for this client are forwarded
+//! out on the same socket rather than needing a separate channel.
+
+use std::sync::Arc;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use futures::{SinkExt, StreamExt};
+use uuid::Uuid;
+
+use crate::notification::NotificationRegistry;
+use crate::server::Server;
+use crate::transport_http::dispatch_any;
+
+struct WsState {
+    server: Server,
+    notifications: Arc<NotificationRegistry>,
+}
+
+/// Create an Axum router exposing the WebSocket transport at `/mcp/ws`,
+/// sharing `server` and `notifications` with whichever other transports
+/// this process also serves.
+pub fn ws_router(server: Server, notifications: Arc<NotificationRegistry>) -> Router {
+    let state = Arc::new(WsState { server, notifications });
+    Router::new().route("/mcp/ws", get(handle_upgrade)).with_state(state)
+}
+
+async fn handle_upgrade(ws: WebSocketUpgrade, State(state): State<Arc<WsState>>) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| handle_socket(socket, state))
+}
+
+/// Drive one connection: concurrently read inbound requests off the
+/// socket and forward this connection's subscription frames out onto it,
+/// until either side closes.
+async fn handle_socket(socket: WebSocket, state: Arc<WsState>) {
+    let (mut sink, mut stream) = socket.split();
+    let owner = Uuid::new_v4().to_string();
+    let (subscription_id, mut notifications) = state.notifications.subscribe(&owner);
+
+    loop {
+        tokio::select! {
+            incoming = stream.next() => {
+                match incoming {
+                    Some(Ok(Message::Text(text))) => {
+                        let Some(reply) = dispatch_any(&state.server, &text).await else { continue };
+                        if sink.send(Message::Text(reply.to_string())).await.is_err() {
+                            break;
+                        }
+                    }
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => {} // ignore ping/pong/binary frames
+                    Some(Err(_)) => break,
+                }
+            }
+            frame = notifications.recv() => {
+                let Some(frame) = frame else { continue };
+                let text = serde_json::to_string(&frame).unwrap();
+                if sink.send(Message::Text(text)).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    state.notifications.unsubscribe(&owner, subscription_id);
+}
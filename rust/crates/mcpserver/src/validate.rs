@@ -0,0 +1,406 @@
+use regex::Regex;
+use serde_json::Value;
+
+use crate::types::Tool;
+
+impl Tool {
+    /// Validate arguments against the tool's input schema metadata.
+    pub fn validate_arguments(&self, args: &Value) -> Result<(), String> {
+        let empty = serde_json::Map::new();
+        let obj = args.as_object().unwrap_or(&empty);
+        let meta = &self.schema_meta;
+
+        // Check required fields.
+        for field in &meta.required {
+            if !obj.contains_key(field) {
+                return Err(format!("missing required field \"{}\"", field));
+            }
+        }
+
+        // Check oneOf — at least one set of required fields must be satisfied.
+        if !meta.one_of.is_empty() {
+            let satisfied = meta.one_of.iter().any(|set| {
+                set.required.iter().all(|f| obj.contains_key(f))
+            });
+            if !satisfied {
+                return Err("arguments must satisfy oneOf requirements".into());
+            }
+        }
+
+        // Check dependencies — if field A is present, fields B must also be present.
+        for (field, deps) in &meta.dependencies {
+            if obj.contains_key(field) {
+                for dep in deps {
+                    if !obj.contains_key(dep) {
+                        return Err(format!(
+                            "field \"{}\" requires \"{}\" to also be present",
+                            field, dep
+                        ));
+                    }
+                }
+            }
+        }
+
+        // Full recursive JSON Schema validation (type, enum, numeric/string/
+        // array constraints, nested objects, combinators) against the raw
+        // schema — `meta` only tracks the handful of keywords above.
+        validate_node(&self.input_schema, args, "")
+    }
+}
+
+/// Recursively validate `value` against `schema`, reporting the failing
+/// field as a dotted path rooted at `path` (e.g. `"geo.lat"`), or `""` for
+/// the document root.
+fn validate_node(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let Some(schema) = schema.as_object() else {
+        return Ok(());
+    };
+
+    if let Some(const_val) = schema.get("const") {
+        if value != const_val {
+            return Err(format!("{}: must equal {}", display_path(path), const_val));
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            return Err(format!("{}: must be one of {}", display_path(path), Value::Array(allowed.clone())));
+        }
+    }
+
+    if let Some(ty) = schema.get("type") {
+        validate_type(ty, value, path)?;
+    }
+
+    match value {
+        Value::Number(n) => validate_number(schema, n, path)?,
+        Value::String(s) => validate_string(schema, s, path)?,
+        Value::Array(items) => validate_array(schema, items, path)?,
+        Value::Object(props) => validate_object(schema, props, path)?,
+        _ => {}
+    }
+
+    if let Some(subschemas) = schema.get("allOf").and_then(Value::as_array) {
+        for sub in subschemas {
+            validate_node(sub, value, path)?;
+        }
+    }
+
+    if let Some(subschemas) = schema.get("anyOf").and_then(Value::as_array) {
+        let matched = subschemas.iter().any(|sub| validate_node(sub, value, path).is_ok());
+        if !matched {
+            return Err(format!("{}: does not match any schema in anyOf", display_path(path)));
+        }
+    }
+
+    if let Some(sub) = schema.get("not") {
+        if validate_node(sub, value, path).is_ok() {
+            return Err(format!("{}: must not match the \"not\" schema", display_path(path)));
+        }
+    }
+
+    Ok(())
+}
+
+/// `type` accepts either a single type name or an array of alternatives.
+fn validate_type(ty: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let matches = |name: &str| match name {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.as_f64().is_some_and(|n| n.fract() == 0.0),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => true, // unknown type names are not enforced
+    };
+
+    let ok = match ty {
+        Value::String(name) => matches(name),
+        Value::Array(names) => names.iter().filter_map(Value::as_str).any(matches),
+        _ => true,
+    };
+
+    if !ok {
+        let expected = match ty {
+            Value::String(name) => name.clone(),
+            other => other.to_string(),
+        };
+        return Err(format!("{}: expected {}, got {}", display_path(path), expected, json_type_name(value)));
+    }
+    Ok(())
+}
+
+fn validate_number(schema: &serde_json::Map<String, Value>, n: &serde_json::Number, path: &str) -> Result<(), String> {
+    let Some(n) = n.as_f64() else { return Ok(()) };
+
+    if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+        if n < min {
+            return Err(format!("{}: must be >= {}", display_path(path), min));
+        }
+    }
+    if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+        if n > max {
+            return Err(format!("{}: must be <= {}", display_path(path), max));
+        }
+    }
+    if let Some(step) = schema.get("multipleOf").and_then(Value::as_f64) {
+        if step > 0.0 && (n / step).fract().abs() > f64::EPSILON {
+            return Err(format!("{}: must be a multiple of {}", display_path(path), step));
+        }
+    }
+    Ok(())
+}
+
+fn validate_string(schema: &serde_json::Map<String, Value>, s: &str, path: &str) -> Result<(), String> {
+    let len = s.chars().count();
+    if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+        if (len as u64) < min {
+            return Err(format!("{}: length must be >= {}", display_path(path), min));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+        if (len as u64) > max {
+            return Err(format!("{}: length must be <= {}", display_path(path), max));
+        }
+    }
+    if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+        let re = Regex::new(pattern).map_err(|e| format!("{}: invalid pattern in schema: {}", display_path(path), e))?;
+        if !re.is_match(s) {
+            return Err(format!("{}: does not match pattern \"{}\"", display_path(path), pattern));
+        }
+    }
+    Ok(())
+}
+
+fn validate_array(schema: &serde_json::Map<String, Value>, items: &[Value], path: &str) -> Result<(), String> {
+    if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+        if (items.len() as u64) < min {
+            return Err(format!("{}: must have at least {} items", display_path(path), min));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(Value::as_u64) {
+        if (items.len() as u64) > max {
+            return Err(format!("{}: must have at most {} items", display_path(path), max));
+        }
+    }
+    if schema.get("uniqueItems").and_then(Value::as_bool).unwrap_or(false) {
+        for (i, a) in items.iter().enumerate() {
+            if items[..i].iter().any(|b| b == a) {
+                return Err(format!("{}: items must be unique", display_path(path)));
+            }
+        }
+    }
+    if let Some(item_schema) = schema.get("items") {
+        for (i, item) in items.iter().enumerate() {
+            validate_node(item_schema, item, &join_path(path, &format!("[{}]", i)))?;
+        }
+    }
+    Ok(())
+}
+
+fn validate_object(schema: &serde_json::Map<String, Value>, props: &serde_json::Map<String, Value>, path: &str) -> Result<(), String> {
+    if let Some(required) = schema.get("required").and_then(Value::as_array) {
+        for field in required.iter().filter_map(Value::as_str) {
+            if !props.contains_key(field) {
+                return Err(format!("{}: missing required field \"{}\"", display_path(path), field));
+            }
+        }
+    }
+
+    let property_schemas = schema.get("properties").and_then(Value::as_object);
+
+    if schema.get("additionalProperties") == Some(&Value::Bool(false)) {
+        let allowed = property_schemas;
+        for key in props.keys() {
+            let known = allowed.is_some_and(|p| p.contains_key(key));
+            if !known {
+                return Err(format!("{}: unexpected additional property \"{}\"", display_path(path), key));
+            }
+        }
+    }
+
+    if let Some(property_schemas) = property_schemas {
+        for (key, sub_schema) in property_schemas {
+            if let Some(value) = props.get(key) {
+                validate_node(sub_schema, value, &join_path(path, key))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join_path(parent: &str, field: &str) -> String {
+    if parent.is_empty() {
+        field.to_string()
+    } else if field.starts_with('[') {
+        format!("{}{}", parent, field)
+    } else {
+        format!("{}.{}", parent, field)
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "(root)"
+    } else {
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::parse_tools;
+
+    fn make_tool(schema_json: &str) -> Tool {
+        let json = format!(
+            r#"[{{"name":"test","description":"test","inputSchema":{}}}]"#,
+            schema_json
+        );
+        let tools = parse_tools(json.as_bytes()).unwrap();
+        tools.into_iter().next().unwrap()
+    }
+
+    #[test]
+    fn test_validate_required_present() {
+        let tool = make_tool(r#"{"type":"object","properties":{},"required":["name"]}"#);
+        let args = serde_json::json!({"name": "hello"});
+        assert!(tool.validate_arguments(&args).is_ok());
+    }
+
+    #[test]
+    fn test_validate_required_missing() {
+        let tool = make_tool(r#"{"type":"object","properties":{},"required":["name"]}"#);
+        let args = serde_json::json!({});
+        let err = tool.validate_arguments(&args).unwrap_err();
+        assert!(err.contains("missing required field"));
+    }
+
+    #[test]
+    fn test_validate_one_of_match() {
+        let tool = make_tool(
+            r#"{"type":"object","properties":{},"oneOf":[{"required":["phone"]},{"required":["email"]}]}"#,
+        );
+        let args = serde_json::json!({"phone": "+1555"});
+        assert!(tool.validate_arguments(&args).is_ok());
+    }
+
+    #[test]
+    fn test_validate_one_of_none_match() {
+        let tool = make_tool(
+            r#"{"type":"object","properties":{},"oneOf":[{"required":["phone"]},{"required":["email"]}]}"#,
+        );
+        let args = serde_json::json!({});
+        let err = tool.validate_arguments(&args).unwrap_err();
+        assert!(err.contains("oneOf"));
+    }
+
+    #[test]
+    fn test_validate_dependencies_satisfied() {
+        let tool = make_tool(
+            r#"{"type":"object","properties":{},"dependencies":{"geo_lat":["geo_lon"]}}"#,
+        );
+        let args = serde_json::json!({"geo_lat": 1.0, "geo_lon": 2.0});
+        assert!(tool.validate_arguments(&args).is_ok());
+    }
+
+    #[test]
+    fn test_validate_dependencies_missing() {
+        let tool = make_tool(
+            r#"{"type":"object","properties":{},"dependencies":{"geo_lat":["geo_lon"]}}"#,
+        );
+        let args = serde_json::json!({"geo_lat": 1.0});
+        let err = tool.validate_arguments(&args).unwrap_err();
+        assert!(err.contains("requires"));
+    }
+
+    #[test]
+    fn test_validate_combined_required_and_one_of() {
+        let tool = make_tool(
+            r#"{"type":"object","properties":{},"required":["code"],"oneOf":[{"required":["phone","code"]},{"required":["email","code"]}]}"#,
+        );
+        let args = serde_json::json!({"code": "123456", "phone": "+1555"});
+        assert!(tool.validate_arguments(&args).is_ok());
+
+        let args2 = serde_json::json!({"phone": "+1555"});
+        assert!(tool.validate_arguments(&args2).is_err());
+    }
+
+    #[test]
+    fn test_validate_type_mismatch_reports_path() {
+        let tool = make_tool(
+            r#"{"type":"object","properties":{"geo":{"type":"object","properties":{"lat":{"type":"number"}}}}}"#,
+        );
+        let args = serde_json::json!({"geo": {"lat": "not a number"}});
+        let err = tool.validate_arguments(&args).unwrap_err();
+        assert_eq!(err, "geo.lat: expected number, got string");
+    }
+
+    #[test]
+    fn test_validate_enum() {
+        let tool = make_tool(
+            r#"{"type":"object","properties":{"unit":{"type":"string","enum":["c","f"]}}}"#,
+        );
+        assert!(tool.validate_arguments(&serde_json::json!({"unit": "c"})).is_ok());
+        assert!(tool.validate_arguments(&serde_json::json!({"unit": "k"})).is_err());
+    }
+
+    #[test]
+    fn test_validate_numeric_bounds() {
+        let tool = make_tool(
+            r#"{"type":"object","properties":{"pct":{"type":"number","minimum":0,"maximum":100}}}"#,
+        );
+        assert!(tool.validate_arguments(&serde_json::json!({"pct": 50})).is_ok());
+        assert!(tool.validate_arguments(&serde_json::json!({"pct": 150})).is_err());
+    }
+
+    #[test]
+    fn test_validate_string_pattern_and_length() {
+        let tool = make_tool(
+            r#"{"type":"object","properties":{"code":{"type":"string","pattern":"^[0-9]{6}$","minLength":6,"maxLength":6}}}"#,
+        );
+        assert!(tool.validate_arguments(&serde_json::json!({"code": "123456"})).is_ok());
+        assert!(tool.validate_arguments(&serde_json::json!({"code": "abcdef"})).is_err());
+    }
+
+    #[test]
+    fn test_validate_array_items_and_unique() {
+        let tool = make_tool(
+            r#"{"type":"object","properties":{"tags":{"type":"array","items":{"type":"string"},"uniqueItems":true}}}"#,
+        );
+        assert!(tool.validate_arguments(&serde_json::json!({"tags": ["a", "b"]})).is_ok());
+        assert!(tool.validate_arguments(&serde_json::json!({"tags": ["a", "a"]})).is_err());
+        assert!(tool.validate_arguments(&serde_json::json!({"tags": ["a", 1]})).is_err());
+    }
+
+    #[test]
+    fn test_validate_additional_properties_false() {
+        let tool = make_tool(
+            r#"{"type":"object","properties":{"name":{"type":"string"}},"additionalProperties":false}"#,
+        );
+        assert!(tool.validate_arguments(&serde_json::json!({"name": "a"})).is_ok());
+        assert!(tool.validate_arguments(&serde_json::json!({"name": "a", "extra": 1})).is_err());
+    }
+
+    #[test]
+    fn test_validate_not_combinator() {
+        let tool = make_tool(
+            r#"{"type":"object","properties":{"value":{"not":{"type":"string"}}}}"#,
+        );
+        assert!(tool.validate_arguments(&serde_json::json!({"value": 1})).is_ok());
+        assert!(tool.validate_arguments(&serde_json::json!({"value": "nope"})).is_err());
+    }
+}
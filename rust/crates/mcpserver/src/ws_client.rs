@@ -0,0 +1,273 @@
+//! Client-side counterpart to [`crate::transport_ws`]'s `/mcp/ws`: a
+//! persistent connection that reconnects and reissues in-flight work
+//! transparently, so code holding a [`WsClient`] handle never has to
+//! notice the underlying socket bounce.
+//!
+//! A background driver task (spawned by [`WsClient::connect`]) owns the
+//! actual `tokio_tungstenite` socket and exchanges frames with
+//! [`WsClient`] over channels; [`WsClient::call`] and
+//! [`WsClient::subscribe`] are the stable interface that survives
+//! reconnects. When the socket drops, the driver reconnects with
+//! exponential backoff, then replays every request id still waiting on a
+//! [`oneshot`] reply and re-issues every active `*/subscribe` call — both
+//! are tracked keyed by request id, so a response that lands for the
+//! original send just as the socket drops resolves the same reply sender
+//! the replay's response otherwise would, and the replay itself is a
+//! no-op from the caller's perspective.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tokio_tungstenite::tungstenite::Message;
+use uuid::Uuid;
+
+/// Initial delay before the first reconnect attempt after a drop; doubles
+/// on each further failure up to [`MAX_BACKOFF`], so a brief blip retries
+/// quickly without spinning the socket on a longer outage.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Backlog of outbound frames queued for the driver while it's between
+/// connections.
+const OUTBOUND_CHANNEL_CAPACITY: usize = 256;
+
+/// Backlog of server-initiated notification frames a slow subscriber
+/// hasn't drained yet.
+const NOTIFICATION_CHANNEL_CAPACITY: usize = 64;
+
+/// Requests and subscriptions the driver must be able to replay across a
+/// reconnect, keyed by the JSON-RPC request id.
+#[derive(Default)]
+struct Inflight {
+    /// Every call awaiting a response, whether an ordinary `call` or a
+    /// `subscribe`'s initial ack — removed as soon as a response with a
+    /// matching id arrives, which is what makes replaying an
+    /// already-answered request harmless.
+    pending: HashMap<String, (Value, oneshot::Sender<Value>)>,
+    /// `*/subscribe` requests that must be reissued on every reconnect
+    /// for as long as the subscription is meant to stay active, even
+    /// after their initial ack has already resolved the `pending` entry.
+    subscriptions: HashMap<String, Value>,
+}
+
+/// A stable handle to a reconnecting `/mcp/ws` connection. Clone freely —
+/// every clone shares the same background driver, in-flight map, and
+/// notification stream.
+#[derive(Clone)]
+pub struct WsClient {
+    inflight: Arc<Mutex<Inflight>>,
+    outbound: mpsc::Sender<Value>,
+    notifications: broadcast::Sender<Value>,
+}
+
+impl WsClient {
+    /// Connect to `url` and spawn the background driver task. Returns
+    /// immediately — the first connection attempt (and every reconnect
+    /// after it) happens on the driver task, not here.
+    pub fn connect(url: impl Into<String>) -> Self {
+        let inflight = Arc::new(Mutex::new(Inflight::default()));
+        let (outbound_tx, outbound_rx) = mpsc::channel(OUTBOUND_CHANNEL_CAPACITY);
+        let (notifications, _) = broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY);
+
+        let client = WsClient { inflight: inflight.clone(), outbound: outbound_tx, notifications: notifications.clone() };
+        tokio::spawn(driver_loop(url.into(), inflight, outbound_rx, notifications));
+        client
+    }
+
+    /// Subscribe to server-initiated notification frames (anything
+    /// inbound with no `id`) — `channel-notify` pushes, progress updates
+    /// on a long-running tool, and so on.
+    pub fn notifications(&self) -> broadcast::Receiver<Value> {
+        self.notifications.subscribe()
+    }
+
+    /// Issue a one-shot JSON-RPC call and await its response. Replayed
+    /// automatically (same request id) if the connection drops before a
+    /// response arrives.
+    pub async fn call(&self, method: impl Into<String>, params: Value) -> Value {
+        let id = Uuid::new_v4().to_string();
+        let frame = json!({"jsonrpc": "2.0", "id": id, "method": method.into(), "params": params});
+        self.send_and_await(id, frame).await
+    }
+
+    /// Issue a `*/subscribe` call and record it as an active subscription
+    /// so it's reissued on every reconnect, not just replayed until its
+    /// first ack. Pair with [`WsClient::unsubscribe`] to stop.
+    pub async fn subscribe(&self, method: impl Into<String>, params: Value) -> Value {
+        let id = Uuid::new_v4().to_string();
+        let frame = json!({"jsonrpc": "2.0", "id": id, "method": method.into(), "params": params});
+        self.inflight.lock().unwrap().subscriptions.insert(id.clone(), frame.clone());
+        self.send_and_await(id, frame).await
+    }
+
+    /// Stop reissuing `sub_id`'s subscribe call on reconnect, and issue
+    /// `method`/`params` (typically the matching `*/unsubscribe`) to tell
+    /// the server the same thing.
+    pub async fn unsubscribe(&self, sub_id: &str, method: impl Into<String>, params: Value) -> Value {
+        self.inflight.lock().unwrap().subscriptions.remove(sub_id);
+        self.call(method, params).await
+    }
+
+    async fn send_and_await(&self, id: String, frame: Value) -> Value {
+        let (tx, rx) = oneshot::channel();
+        self.inflight.lock().unwrap().pending.insert(id, (frame.clone(), tx));
+        if self.outbound.send(frame).await.is_err() {
+            return Value::Null;
+        }
+        rx.await.unwrap_or(Value::Null)
+    }
+}
+
+/// Owns the socket: connects, replays in-flight work, then pumps outbound
+/// frames and inbound frames until the socket drops, at which point it
+/// reconnects with backoff and does it all again. Returns once every
+/// [`WsClient`] handle (and thus the outbound sender) has been dropped.
+async fn driver_loop(
+    url: String,
+    inflight: Arc<Mutex<Inflight>>,
+    mut outbound: mpsc::Receiver<Value>,
+    notifications: broadcast::Sender<Value>,
+) {
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+        let socket = match tokio_tungstenite::connect_async(&url).await {
+            Ok((socket, _)) => socket,
+            Err(_) => {
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+                continue;
+            }
+        };
+        backoff = INITIAL_BACKOFF;
+        let (mut sink, mut stream) = socket.split();
+
+        let replay: Vec<Value> = {
+            let guard = inflight.lock().unwrap();
+            guard
+                .pending
+                .values()
+                .map(|(frame, _)| frame.clone())
+                .chain(guard.subscriptions.values().cloned())
+                .collect()
+        };
+        let mut replay_failed = false;
+        for frame in replay {
+            if sink.send(Message::Text(frame.to_string())).await.is_err() {
+                replay_failed = true;
+                break;
+            }
+        }
+        if replay_failed {
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+            continue;
+        }
+
+        loop {
+            tokio::select! {
+                outgoing = outbound.recv() => {
+                    match outgoing {
+                        Some(frame) => {
+                            if sink.send(Message::Text(frame.to_string())).await.is_err() {
+                                break;
+                            }
+                        }
+                        None => return,
+                    }
+                }
+                incoming = stream.next() => {
+                    match incoming {
+                        Some(Ok(Message::Text(text))) => handle_inbound(&text, &inflight, &notifications),
+                        Some(Ok(Message::Close(_))) | None => break,
+                        Some(Ok(_)) => {} // ignore ping/pong/binary frames
+                        Some(Err(_)) => break,
+                    }
+                }
+            }
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Route one inbound text frame: a response resolves (and removes) its
+/// matching `pending` entry by id — a second response for the same id
+/// (e.g. one arriving late from a connection that's since been replaced)
+/// finds nothing left to resolve and is silently dropped. Anything
+/// without an `id` is a server-initiated notification, broadcast to every
+/// [`WsClient::notifications`] receiver.
+fn handle_inbound(text: &str, inflight: &Arc<Mutex<Inflight>>, notifications: &broadcast::Sender<Value>) {
+    let Ok(value) = serde_json::from_str::<Value>(text) else { return };
+
+    match value.get("id") {
+        Some(Value::String(id)) => {
+            if let Some((_, reply)) = inflight.lock().unwrap().pending.remove(id) {
+                let _ = reply.send(value);
+            }
+        }
+        Some(id) if !id.is_null() => {
+            let id = id.to_string();
+            if let Some((_, reply)) = inflight.lock().unwrap().pending.remove(&id) {
+                let _ = reply.send(value);
+            }
+        }
+        _ => {
+            let _ = notifications.send(value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_inbound_resolves_matching_pending_entry() {
+        let inflight = Arc::new(Mutex::new(Inflight::default()));
+        let (tx, rx) = oneshot::channel();
+        inflight.lock().unwrap().pending.insert("req-1".into(), (json!({}), tx));
+        let (notifications, _) = broadcast::channel(4);
+
+        handle_inbound(&json!({"jsonrpc": "2.0", "id": "req-1", "result": {"ok": true}}).to_string(), &inflight, &notifications);
+
+        assert!(inflight.lock().unwrap().pending.is_empty());
+        let resp = rx.try_recv().unwrap();
+        assert_eq!(resp["result"]["ok"], true);
+    }
+
+    #[test]
+    fn test_handle_inbound_dedupes_duplicate_response_for_same_id() {
+        let inflight = Arc::new(Mutex::new(Inflight::default()));
+        let (tx, rx) = oneshot::channel();
+        inflight.lock().unwrap().pending.insert("req-1".into(), (json!({}), tx));
+        let (notifications, _) = broadcast::channel(4);
+
+        let frame = json!({"jsonrpc": "2.0", "id": "req-1", "result": {}}).to_string();
+        handle_inbound(&frame, &inflight, &notifications);
+        // A second copy of the same response (e.g. a stale connection's
+        // reply arriving after the replay's already resolved it) finds no
+        // pending entry left and is dropped rather than panicking on a
+        // closed oneshot sender.
+        handle_inbound(&frame, &inflight, &notifications);
+
+        assert!(rx.try_recv().is_ok());
+    }
+
+    #[test]
+    fn test_handle_inbound_without_id_is_broadcast_as_notification() {
+        let inflight = Arc::new(Mutex::new(Inflight::default()));
+        let (notifications, mut rx) = broadcast::channel(4);
+
+        let frame = json!({"jsonrpc": "2.0", "method": "notifications/message", "params": {"text": "hi"}}).to_string();
+        handle_inbound(&frame, &inflight, &notifications);
+
+        let received = rx.try_recv().unwrap();
+        assert_eq!(received["method"], "notifications/message");
+    }
+}
@@ -0,0 +1,221 @@
+//! Pluggable request authentication.
+//!
+//! [`Authenticator`] turns transport headers into the `context` `Value`
+//! tool handlers receive, so the crate owns claims extraction instead of
+//! every consumer hand-writing a middleware like the one in
+//! `examples/cognito_server.rs`. [`JwtAuthenticator`] is the provided JWT
+//! implementation; it's generic over the transport, so stdio or any other
+//! transport can reuse it via [`Authenticator::authenticate`] directly.
+//! [`MultiIssuerAuthenticator`] generalizes it to a registry of issuers for
+//! SaaS multi-tenancy.
+
+use async_trait::async_trait;
+use http::HeaderMap;
+use jsonwebtoken::{decode, decode_header, Algorithm, Validation};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::jwks::JwksKeyManager;
+use crate::types::McpError;
+
+/// Extracts request-scoped claims from transport headers for use as MCP
+/// tool `context`. Implement this directly for custom auth schemes, or use
+/// [`JwtAuthenticator`] for bearer-JWT validation against a JWKS.
+#[async_trait]
+pub trait Authenticator: Send + Sync {
+    /// Validate the request and return the claims to pass as tool context.
+    /// Return `Err` to reject the request before it reaches any handler.
+    async fn authenticate(&self, headers: &HeaderMap) -> Result<Value, McpError>;
+}
+
+/// Pull the bearer token out of an `Authorization` header.
+fn bearer_token(headers: &HeaderMap) -> Result<&str, McpError> {
+    headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .strip_prefix("Bearer ")
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| McpError::Other("missing bearer token".into()))
+}
+
+/// Decode the `iss` claim from a JWT's payload without verifying its
+/// signature, so the issuer can be used to select *which* key and
+/// validation rules to verify the signature with.
+fn peek_issuer(token: &str) -> Result<String, McpError> {
+    let mut insecure = Validation::new(Algorithm::RS256);
+    insecure.insecure_disable_signature_validation();
+    insecure.validate_exp = false;
+    insecure.required_spec_claims.clear();
+    let dummy_key = jsonwebtoken::DecodingKey::from_secret(&[]);
+    let claims: Value = decode(token, &dummy_key, &insecure)
+        .map_err(|e| McpError::Other(format!("invalid jwt: {}", e)))?
+        .claims;
+    claims
+        .get("iss")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .ok_or_else(|| McpError::Other("jwt missing iss".into()))
+}
+
+/// JWT [`Authenticator`] backed by a [`JwksKeyManager`], validating
+/// signature, issuer, and (optionally) audience.
+pub struct JwtAuthenticator {
+    keys: Arc<JwksKeyManager>,
+    issuers: Vec<String>,
+    audience: Option<String>,
+}
+
+impl JwtAuthenticator {
+    /// `issuers` lists the accepted `iss` values; the token must match one.
+    pub fn new(keys: Arc<JwksKeyManager>, issuers: Vec<String>) -> Self {
+        Self {
+            keys,
+            issuers,
+            audience: None,
+        }
+    }
+
+    /// Require the given `aud` value (e.g. a Cognito app client ID).
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+}
+
+#[async_trait]
+impl Authenticator for JwtAuthenticator {
+    async fn authenticate(&self, headers: &HeaderMap) -> Result<Value, McpError> {
+        let token = bearer_token(headers)?;
+
+        let header = decode_header(token)
+            .map_err(|e| McpError::Other(format!("invalid jwt header: {}", e)))?;
+        let kid = header
+            .kid
+            .ok_or_else(|| McpError::Other("jwt missing kid".into()))?;
+
+        let decoding_key = self
+            .keys
+            .key_for(&kid)
+            .await
+            .ok_or_else(|| McpError::Other(format!("unknown kid: {}", kid)))?;
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_issuer(&self.issuers);
+        match &self.audience {
+            Some(aud) => validation.set_audience(&[aud]),
+            None => validation.validate_aud = false,
+        }
+
+        let token_data = decode::<Value>(token, &decoding_key, &validation)
+            .map_err(|e| McpError::Other(format!("jwt validation failed: {}", e)))?;
+
+        Ok(token_data.claims)
+    }
+}
+
+/// Per-issuer configuration registered with a [`MultiIssuerAuthenticator`].
+pub struct IssuerConfig {
+    keys: Arc<JwksKeyManager>,
+    audience: Option<String>,
+    tenant_id: Option<String>,
+}
+
+impl IssuerConfig {
+    pub fn new(keys: Arc<JwksKeyManager>) -> Self {
+        Self {
+            keys,
+            audience: None,
+            tenant_id: None,
+        }
+    }
+
+    /// Require the given `aud` value for tokens from this issuer.
+    pub fn with_audience(mut self, audience: impl Into<String>) -> Self {
+        self.audience = Some(audience.into());
+        self
+    }
+
+    /// Tenant metadata to surface into `context["tenant_id"]` for tokens
+    /// validated against this issuer (e.g. the Cognito pool that maps to a
+    /// given customer), independent of any `custom:tenant_id` claim.
+    pub fn with_tenant_id(mut self, tenant_id: impl Into<String>) -> Self {
+        self.tenant_id = Some(tenant_id.into());
+        self
+    }
+}
+
+/// Multi-issuer [`Authenticator`] for SaaS deployments that federate
+/// several Cognito pools (or mix Cognito with other OIDC providers).
+///
+/// Tokens are routed to their issuer's [`IssuerConfig`] by decoding the
+/// (unverified) `iss` claim first; an issuer that isn't registered is
+/// rejected before any signature verification is attempted. The resolved
+/// tenant — from [`IssuerConfig::with_tenant_id`], falling back to the
+/// token's own `custom:tenant_id` claim — is merged into the returned
+/// context as `tenant_id` so tools can scope data per tenant without
+/// re-parsing the token.
+#[derive(Default)]
+pub struct MultiIssuerAuthenticator {
+    issuers: HashMap<String, IssuerConfig>,
+}
+
+impl MultiIssuerAuthenticator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `issuer` (the exact `iss` claim value) with its validation
+    /// config. Replaces any existing registration for the same issuer.
+    pub fn with_issuer(mut self, issuer: impl Into<String>, config: IssuerConfig) -> Self {
+        self.issuers.insert(issuer.into(), config);
+        self
+    }
+}
+
+#[async_trait]
+impl Authenticator for MultiIssuerAuthenticator {
+    async fn authenticate(&self, headers: &HeaderMap) -> Result<Value, McpError> {
+        let token = bearer_token(headers)?;
+        let issuer = peek_issuer(token)?;
+
+        let config = self
+            .issuers
+            .get(&issuer)
+            .ok_or_else(|| McpError::Other(format!("unregistered issuer: {}", issuer)))?;
+
+        let header = decode_header(token)
+            .map_err(|e| McpError::Other(format!("invalid jwt header: {}", e)))?;
+        let kid = header
+            .kid
+            .ok_or_else(|| McpError::Other("jwt missing kid".into()))?;
+
+        let decoding_key = config
+            .keys
+            .key_for(&kid)
+            .await
+            .ok_or_else(|| McpError::Other(format!("unknown kid: {}", kid)))?;
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_issuer(&[&issuer]);
+        match &config.audience {
+            Some(aud) => validation.set_audience(&[aud]),
+            None => validation.validate_aud = false,
+        }
+
+        let token_data = decode::<Value>(token, &decoding_key, &validation)
+            .map_err(|e| McpError::Other(format!("jwt validation failed: {}", e)))?;
+
+        let mut claims = token_data.claims;
+        let tenant_id = config
+            .tenant_id
+            .clone()
+            .or_else(|| claims.get("custom:tenant_id").and_then(|v| v.as_str()).map(String::from));
+        if let (Some(tenant_id), Some(map)) = (tenant_id, claims.as_object_mut()) {
+            map.insert("tenant_id".into(), Value::String(tenant_id));
+        }
+
+        Ok(claims)
+    }
+}
@@ -0,0 +1,43 @@
+//! Ready-made Axum integration for [`crate::Authenticator`].
+//!
+//! Requires the `axum` feature. Wraps `auth_middleware` around your `/mcp`
+//! route and the decoded claims arrive as an `Extension<Value>` — the few
+//! lines below replace the ~60-line hand-rolled middleware every consumer
+//! used to write.
+//!
+//! ```ignore
+//! let app = Router::new().route(
+//!     "/mcp",
+//!     post(handle_mcp).layer(middleware::from_fn_with_state(
+//!         authenticator,
+//!         mcpserver::axum_ext::auth_middleware,
+//!     )),
+//! );
+//! ```
+
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::Response;
+
+use crate::auth::Authenticator;
+
+/// Axum middleware that runs `authenticator.authenticate()` against the
+/// request headers and, on success, inserts the resulting claims `Value`
+/// as an `Extension` for downstream handlers (e.g. your `/mcp` handler,
+/// which passes it straight through to [`crate::Server::handle`]).
+pub async fn auth_middleware(
+    State(authenticator): State<Arc<dyn Authenticator>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let claims = authenticator
+        .authenticate(req.headers())
+        .await
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+
+    req.extensions_mut().insert(claims);
+    Ok(next.run(req).await)
+}
@@ -0,0 +1,134 @@
+//! Rotation-safe JWKS (JSON Web Key Set) key manager.
+//!
+//! Wraps a JWKS URL as a shared `kid -> DecodingKey` cache that refreshes
+//! itself in the background, so a long-running server keeps validating
+//! tokens across a signing-key rotation instead of requiring a restart.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use jsonwebtoken::DecodingKey;
+use serde::Deserialize;
+use tokio::sync::{Mutex, RwLock};
+
+use crate::types::McpError;
+
+/// Minimum gap between two refreshes triggered by a cache miss, so a burst
+/// of tokens signed with an unknown `kid` can't stampede the JWKS endpoint.
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Deserialize)]
+struct JwkSet {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Jwk {
+    kid: String,
+    n: String,
+    e: String,
+}
+
+/// Shared, rotation-safe cache of `kid -> DecodingKey` backed by a JWKS URL.
+///
+/// Construct with [`JwksKeyManager::spawn`] and share the returned `Arc`
+/// across requests. A background task re-fetches the JWKS on a fixed
+/// interval; a cache miss for an unknown `kid` also triggers a refresh,
+/// guarded by [`MIN_REFRESH_INTERVAL`] and coalesced across concurrent
+/// callers so a burst of unknown-`kid` tokens issues at most one extra
+/// fetch per window.
+pub struct JwksKeyManager {
+    url: String,
+    keys: RwLock<HashMap<String, DecodingKey>>,
+    last_refresh: Mutex<Instant>,
+    refresh_lock: Mutex<()>,
+}
+
+impl JwksKeyManager {
+    /// Fetch the JWKS once, then spawn a background task that re-fetches
+    /// every `refresh_interval` for as long as the returned `Arc` is held.
+    pub async fn spawn(
+        jwks_url: impl Into<String>,
+        refresh_interval: Duration,
+    ) -> Result<Arc<Self>, McpError> {
+        let manager = Arc::new(Self {
+            url: jwks_url.into(),
+            keys: RwLock::new(HashMap::new()),
+            last_refresh: Mutex::new(Instant::now() - MIN_REFRESH_INTERVAL),
+            refresh_lock: Mutex::new(()),
+        });
+
+        manager.refresh().await?;
+
+        let bg = Arc::downgrade(&manager);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(refresh_interval);
+            ticker.tick().await; // first tick fires immediately; we just fetched above
+            loop {
+                ticker.tick().await;
+                let Some(bg) = bg.upgrade() else { break };
+                if let Err(e) = bg.refresh().await {
+                    tracing::warn!("jwks background refresh failed: {}", e);
+                }
+            }
+        });
+
+        Ok(manager)
+    }
+
+    /// Resolve a `kid` to its decoding key, refreshing out-of-band on a miss.
+    pub async fn key_for(&self, kid: &str) -> Option<DecodingKey> {
+        if let Some(key) = self.keys.read().await.get(kid) {
+            return Some(key.clone());
+        }
+
+        // Unknown kid — the key set may have rotated since our last fetch.
+        if self.due_for_refresh().await {
+            if let Err(e) = self.refresh().await {
+                tracing::warn!("jwks on-demand refresh failed: {}", e);
+            }
+        }
+
+        self.keys.read().await.get(kid).cloned()
+    }
+
+    async fn due_for_refresh(&self) -> bool {
+        self.last_refresh.lock().await.elapsed() >= MIN_REFRESH_INTERVAL
+    }
+
+    /// Re-fetch the JWKS and replace the cached key set.
+    ///
+    /// Serialized by `refresh_lock` so concurrent miss-triggered callers
+    /// coalesce into one in-flight HTTP request: the first caller through
+    /// does the fetch, the rest find the cache already fresh once they
+    /// acquire the lock and skip it.
+    async fn refresh(&self) -> Result<(), McpError> {
+        let _guard = self.refresh_lock.lock().await;
+
+        if self.last_refresh.lock().await.elapsed() < MIN_REFRESH_INTERVAL {
+            return Ok(());
+        }
+
+        let jwks: JwkSet = reqwest::get(&self.url)
+            .await
+            .map_err(|e| McpError::Other(format!("jwks fetch: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| McpError::Other(format!("jwks parse: {}", e)))?;
+
+        let mut keys = HashMap::with_capacity(jwks.keys.len());
+        for jwk in &jwks.keys {
+            match DecodingKey::from_rsa_components(&jwk.n, &jwk.e) {
+                Ok(key) => {
+                    keys.insert(jwk.kid.clone(), key);
+                }
+                Err(e) => tracing::warn!("jwks: skipping key {}: {}", jwk.kid, e),
+            }
+        }
+
+        *self.keys.write().await = keys;
+        *self.last_refresh.lock().await = Instant::now();
+        Ok(())
+    }
+}
@@ -16,28 +16,39 @@
 //!     .server_info("my-server", "0.1.0")
 //!     .build();
 //!
-//! server.handle_tool("echo", FnToolHandler::new(|args: Value| async move {
+//! server.handle_tool("echo", FnToolHandler::new(|args: Value, _context: Value, _state| async move {
 //!     let msg = args.get("message").and_then(|v| v.as_str()).unwrap_or("");
 //!     Ok(text_result(msg))
 //! }));
 //!
 //! // Use from any HTTP framework — just deserialize the body and call handle():
 //! let req: JsonRpcRequest = serde_json::from_str(r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#).unwrap();
-//! let resp = server.handle(req).await;
+//! let resp = server.handle(req, Value::Null).await;
 //! // resp implements Serialize — pass it to axum::Json, serde_json, etc.
 //! let json = serde_json::to_string(&resp).unwrap();
 //! # }
 //! ```
 
+pub mod auth;
+#[cfg(feature = "axum")]
+pub mod axum_ext;
+pub mod jwks;
 pub mod loader;
+pub mod revocation;
 pub mod server;
+pub mod subscriptions;
 pub mod types;
 mod validate;
 
 // Re-export the most commonly used items at the crate root.
+pub use auth::{Authenticator, IssuerConfig, JwtAuthenticator, MultiIssuerAuthenticator};
+pub use jwks::JwksKeyManager;
 pub use loader::{load_resources, load_tools, parse_resources, parse_tools};
-pub use server::{FnToolHandler, ResourceHandler, Server, ServerBuilder, ToolHandler};
+pub use revocation::{InMemoryRevocationStore, RevocationStore};
+pub use server::{FnToolHandler, ResourceHandler, Server, ServerBuilder, State, ToolHandler, ToolPolicy};
+pub use subscriptions::{Notifier, ResourceSubscriptions};
 pub use types::{
-    error_result, new_error_response, text_result, ContentBlock, JsonRpcRequest, JsonRpcResponse,
-    McpError, McpResponse, Resource, ResourceContent, RpcError, Tool, ToolResult, PROTOCOL_VERSION,
+    error_result, new_error_response, text_result, BatchResponse, ContentBlock, JsonRpcMessage,
+    JsonRpcRequest, JsonRpcResponse, McpError, McpResponse, Resource, ResourceContent, RpcError,
+    Tool, ToolResult, PROTOCOL_VERSION,
 };
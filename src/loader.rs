@@ -48,7 +48,7 @@ pub fn parse_resources(data: &[u8]) -> Result<Vec<Resource>, McpError> {
 }
 
 /// Extract validation metadata from a JSON Schema object.
-fn parse_schema_meta(schema: &Value) -> SchemaMeta {
+pub(crate) fn parse_schema_meta(schema: &Value) -> SchemaMeta {
     let mut meta = SchemaMeta::default();
 
     if let Some(arr) = schema.get("required").and_then(|v| v.as_array()) {
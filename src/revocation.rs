@@ -0,0 +1,81 @@
+//! Token revocation via a `jti` denylist, for logout and compromised-token
+//! scenarios where a signature- and expiry-valid JWT must still be rejected.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use dashmap::DashMap;
+
+/// Stores revoked token IDs (`jti` claims).
+///
+/// Register an implementation with [`crate::server::ServerBuilder::revocation_store`];
+/// [`crate::Server::handle`] consults it for every request whose `context`
+/// carries a `jti` claim.
+pub trait RevocationStore: Send + Sync {
+    /// Mark `jti` as revoked. `expires_at` is the token's own expiry (unix
+    /// seconds) so the store can self-prune the entry once it would have
+    /// expired anyway, keeping the set bounded.
+    fn revoke(&self, jti: &str, expires_at: u64);
+
+    /// True if `jti` has been revoked and the revocation hasn't self-pruned.
+    fn is_revoked(&self, jti: &str) -> bool;
+}
+
+/// In-memory `RevocationStore` backed by a `DashMap<jti, expires_at>`.
+#[derive(Default)]
+pub struct InMemoryRevocationStore {
+    revoked: DashMap<String, u64>,
+}
+
+impl InMemoryRevocationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+}
+
+impl RevocationStore for InMemoryRevocationStore {
+    fn revoke(&self, jti: &str, expires_at: u64) {
+        self.revoked.insert(jti.to_string(), expires_at);
+    }
+
+    fn is_revoked(&self, jti: &str) -> bool {
+        match self.revoked.get(jti) {
+            Some(expires_at) if *expires_at <= Self::now() => {
+                drop(expires_at);
+                // The token would have expired on its own by now — drop it
+                // from the set instead of carrying it forever.
+                self.revoked.remove(jti);
+                false
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_revoke_and_check() {
+        let store = InMemoryRevocationStore::new();
+        assert!(!store.is_revoked("abc"));
+        store.revoke("abc", InMemoryRevocationStore::now() + 3600);
+        assert!(store.is_revoked("abc"));
+    }
+
+    #[test]
+    fn test_expired_revocation_self_prunes() {
+        let store = InMemoryRevocationStore::new();
+        store.revoke("abc", InMemoryRevocationStore::now() - 1);
+        assert!(!store.is_revoked("abc"));
+        assert!(store.revoked.is_empty());
+    }
+}
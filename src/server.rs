@@ -1,29 +1,60 @@
+use std::any::Any;
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
 use serde_json::value::RawValue;
 use serde_json::{json, Value};
 use tracing;
 
+use crate::auth::Authenticator;
 use crate::loader;
+use crate::revocation::RevocationStore;
+use crate::subscriptions::{Notifier, ResourceSubscriptions};
 use crate::types::*;
 
+/// Type-erased, server-wide application state (a DB pool, HTTP client,
+/// cache, ...) injected into every tool/resource handler call alongside the
+/// per-request `context`. Register with [`ServerBuilder::with_state`];
+/// recover the concrete type a handler registered with [`State::get`].
+/// Cheap to pass by reference or clone — it's just an `Option<Arc<_>>`.
+#[derive(Clone, Default)]
+pub struct State(Option<Arc<dyn Any + Send + Sync>>);
+
+impl State {
+    fn new(inner: Option<Arc<dyn Any + Send + Sync>>) -> Self {
+        State(inner)
+    }
+
+    /// Downcast to `T`, the type passed to [`ServerBuilder::with_state`].
+    /// `None` if no state was registered, or it was registered as some
+    /// other type.
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.0.clone()?.downcast::<T>().ok()
+    }
+}
+
 /// Handler trait for MCP tools. Implement this or use closures.
 ///
 /// The `context` parameter carries request-scoped data from the HTTP layer
 /// (e.g. decoded JWT claims).  It is moved into the handler — zero clones.
+/// `state` carries whatever was registered via
+/// [`ServerBuilder::with_state`], shared across every call.
 #[async_trait]
 pub trait ToolHandler: Send + Sync {
-    async fn call(&self, args: Value, context: Value) -> Result<ToolResult, McpError>;
+    async fn call(&self, args: Value, context: Value, state: &State) -> Result<ToolResult, McpError>;
 }
 
 /// Handler trait for MCP resources.
 ///
 /// The `context` parameter carries request-scoped data from the HTTP layer.
+/// `state` carries whatever was registered via
+/// [`ServerBuilder::with_state`], shared across every call.
 #[async_trait]
 pub trait ResourceHandler: Send + Sync {
-    async fn call(&self, uri: &str, context: Value) -> Result<ResourceContent, McpError>;
+    async fn call(&self, uri: &str, context: Value, state: &State) -> Result<ResourceContent, McpError>;
 }
 
 /// Wraps an async closure into a ToolHandler.
@@ -33,7 +64,7 @@ pub struct FnToolHandler<F> {
 
 impl<F, Fut> FnToolHandler<F>
 where
-    F: Fn(Value, Value) -> Fut + Send + Sync + 'static,
+    F: Fn(Value, Value, State) -> Fut + Send + Sync + 'static,
     Fut: std::future::Future<Output = Result<ToolResult, McpError>> + Send + 'static,
 {
     pub fn new(f: F) -> Arc<dyn ToolHandler> {
@@ -44,11 +75,95 @@ where
 #[async_trait]
 impl<F, Fut> ToolHandler for FnToolHandler<F>
 where
-    F: Fn(Value, Value) -> Fut + Send + Sync + 'static,
+    F: Fn(Value, Value, State) -> Fut + Send + Sync + 'static,
     Fut: std::future::Future<Output = Result<ToolResult, McpError>> + Send + 'static,
 {
-    async fn call(&self, args: Value, context: Value) -> Result<ToolResult, McpError> {
-        (self.f)(args, context).await
+    async fn call(&self, args: Value, context: Value, state: &State) -> Result<ToolResult, McpError> {
+        (self.f)(args, context, state.clone()).await
+    }
+}
+
+/// Wraps an async closure that receives arguments already deserialized into
+/// `T` instead of a raw `Value`. Pairs with [`ServerBuilder::typed_tool`],
+/// which derives the tool's advertised `inputSchema` from the same `T` via
+/// `schemars::schema_for!` so handler, validation, and schema can't drift
+/// apart.
+///
+/// Deserialization failures surface as [`ERR_CODE_BAD_PARAMS`] (via
+/// [`McpError::Validation`]) before the wrapped closure ever runs — see
+/// [`Server::handle_tools_call`](Server) for where that's translated into a
+/// response.
+pub struct TypedToolHandler<T, F> {
+    f: F,
+    _marker: std::marker::PhantomData<fn(T)>,
+}
+
+impl<T, F, Fut> TypedToolHandler<T, F>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+    F: Fn(T, Value, State) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Result<ToolResult, McpError>> + Send + 'static,
+{
+    pub fn new(f: F) -> Arc<dyn ToolHandler> {
+        Arc::new(Self {
+            f,
+            _marker: std::marker::PhantomData,
+        })
+    }
+}
+
+#[async_trait]
+impl<T, F, Fut> ToolHandler for TypedToolHandler<T, F>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+    F: Fn(T, Value, State) -> Fut + Send + Sync + 'static,
+    Fut: std::future::Future<Output = Result<ToolResult, McpError>> + Send + 'static,
+{
+    async fn call(&self, args: Value, context: Value, state: &State) -> Result<ToolResult, McpError> {
+        let typed: T = serde_json::from_value(args)
+            .map_err(|e| McpError::Validation(format!("invalid params: {}", e)))?;
+        (self.f)(typed, context, state.clone()).await
+    }
+}
+
+/// Declarative authorization policy for a tool, evaluated against the
+/// request `context` before `ToolHandler::call` runs.
+///
+/// Register with [`Server::handle_tool_with_policy`]. On failure
+/// `Server::handle` short-circuits with [`ERR_CODE_FORBIDDEN`] instead of
+/// invoking the handler, so handlers don't need to re-implement the check.
+#[derive(Debug, Clone)]
+pub enum ToolPolicy {
+    /// Caller's `cognito:groups` claim must contain at least one of these.
+    AnyGroup(Vec<String>),
+    /// Caller's space-delimited `scope` claim (standard OAuth2 access token
+    /// scopes) must contain at least one of these.
+    AnyScope(Vec<String>),
+}
+
+impl ToolPolicy {
+    fn is_satisfied_by(&self, context: &Value) -> bool {
+        match self {
+            ToolPolicy::AnyGroup(required) => context
+                .get("cognito:groups")
+                .and_then(|v| v.as_array())
+                .map(|groups| {
+                    groups
+                        .iter()
+                        .filter_map(|v| v.as_str())
+                        .any(|g| required.iter().any(|want| want == g))
+                })
+                .unwrap_or(false),
+            ToolPolicy::AnyScope(required) => context
+                .get("scope")
+                .and_then(|v| v.as_str())
+                .map(|scopes| {
+                    scopes
+                        .split(' ')
+                        .any(|tok| required.iter().any(|want| want == tok))
+                })
+                .unwrap_or(false),
+        }
     }
 }
 
@@ -57,13 +172,38 @@ pub struct Server {
     pub(crate) tools: HashMap<String, Tool>,
     pub(crate) resources: HashMap<String, Resource>,
     pub(crate) tool_handlers: HashMap<String, Arc<dyn ToolHandler>>,
+    pub(crate) tool_policies: HashMap<String, ToolPolicy>,
     pub(crate) resource_handlers: HashMap<String, Arc<dyn ResourceHandler>>,
-    /// Pre-serialized initialize result — shared by reference, never copied.
-    initialize_result: Arc<RawValue>,
+    /// Registered revocation store, if any. Consulted by `handle` for every
+    /// request whose `context` carries a `jti` claim.
+    revocation_store: Option<Arc<dyn RevocationStore>>,
+    /// Whether `resources/subscribe` is advertised and accepted. Set via
+    /// [`ServerBuilder::enable_resource_subscriptions`].
+    subscriptions_enabled: bool,
+    /// Per-resource-URI subscriber sets, keyed by the connection id found at
+    /// `context["connectionId"]`.
+    subscriptions: ResourceSubscriptions,
+    /// Outbound notifier per connection id, attached by the transport via
+    /// [`Server::attach_notifier`] and removed via [`Server::detach_notifier`].
+    notifiers: dashmap::DashMap<String, Arc<dyn Notifier>>,
+    /// Pre-serialized initialize result per supported protocol version, keyed
+    /// by version string. `handle_initialize` negotiates against the
+    /// client's requested `protocolVersion`, so this can no longer be a
+    /// single static `RawValue` — but the common case (the client asks for a
+    /// version we have) stays a zero-copy `Arc::clone`.
+    initialize_results: HashMap<String, Arc<RawValue>>,
+    /// Newest entry in `initialize_results` (by lexicographic order, which
+    /// matches chronological order for the `YYYY-MM-DD`-style MCP version
+    /// strings). Returned when the client's requested version isn't one we
+    /// support, so it can decide whether to proceed.
+    newest_protocol_version: String,
     /// Pre-serialized tools/list result.
     tools_list_result: Arc<RawValue>,
     /// Pre-serialized resources/list result.
     resources_list_result: Arc<RawValue>,
+    /// Server-wide application state, shared across every handler call. Set
+    /// via [`ServerBuilder::with_state`].
+    state: State,
 }
 
 impl Server {
@@ -77,11 +217,67 @@ impl Server {
         self.tool_handlers.insert(name.into(), handler);
     }
 
+    /// Register a tool handler that requires the given [`ToolPolicy`] to be
+    /// satisfied by the request `context` before it runs.
+    pub fn handle_tool_with_policy(
+        &mut self,
+        name: impl Into<String>,
+        handler: Arc<dyn ToolHandler>,
+        policy: ToolPolicy,
+    ) {
+        let name = name.into();
+        self.tool_policies.insert(name.clone(), policy);
+        self.tool_handlers.insert(name, handler);
+    }
+
     /// Register a resource handler.
     pub fn handle_resource(&mut self, name: impl Into<String>, handler: Arc<dyn ResourceHandler>) {
         self.resource_handlers.insert(name.into(), handler);
     }
 
+    /// Mark `jti` as revoked, e.g. from a logout tool handler.
+    ///
+    /// No-op (with a warning) if no [`RevocationStore`] was registered via
+    /// [`ServerBuilder::revocation_store`].
+    pub fn revoke(&self, jti: &str, expires_at: u64) {
+        match &self.revocation_store {
+            Some(store) => store.revoke(jti, expires_at),
+            None => tracing::warn!("Server::revoke called but no revocation store is registered"),
+        }
+    }
+
+    /// Attach `notifier` as the outbound sink for `connection_id`, so
+    /// [`Self::notify_resource_updated`] can reach any subscriptions that
+    /// connection registers via `resources/subscribe`.
+    ///
+    /// Call this when the transport opens a push-capable connection (e.g.
+    /// an SSE stream) and pass the same `connection_id` in `context` for
+    /// every `handle`/`handle_batch` call on that connection.
+    pub fn attach_notifier(&self, connection_id: impl Into<String>, notifier: Arc<dyn Notifier>) {
+        self.notifiers.insert(connection_id.into(), notifier);
+    }
+
+    /// Detach `connection_id`'s notifier and drop all of its resource
+    /// subscriptions. Call this when the connection closes, or subscriptions
+    /// accumulate for connections that will never be notified again.
+    pub fn detach_notifier(&self, connection_id: &str) {
+        self.notifiers.remove(connection_id);
+        self.subscriptions.unsubscribe_all(connection_id);
+    }
+
+    /// Push a `notifications/resources/updated` notification to every
+    /// connection currently subscribed to `uri` that still has a notifier
+    /// attached.
+    pub async fn notify_resource_updated(&self, uri: &str) {
+        for connection_id in self.subscriptions.subscribers(uri) {
+            if let Some(notifier) = self.notifiers.get(&connection_id).map(|n| n.clone()) {
+                notifier
+                    .notify("notifications/resources/updated", json!({ "uri": uri }))
+                    .await;
+            }
+        }
+    }
+
     /// Route a JSON-RPC request to the appropriate MCP handler.
     ///
     /// Takes ownership of the request and context, moving fields into
@@ -93,18 +289,79 @@ impl Server {
     /// runs — no cloning.  For cached endpoints it is simply dropped.
     /// Pass `Value::Null` or `json!({})` when there is no context.
     pub async fn handle(&self, req: JsonRpcRequest, context: Value) -> McpResponse {
+        self.handle_with_shared_context(req, &Arc::new(context)).await
+    }
+
+    /// Dispatch a JSON-RPC 2.0 batch: each member runs through the same
+    /// routing as [`Self::handle`], concurrently (`futures::future::join_all`),
+    /// sharing one `context` via `Arc` rather than cloning it up front for
+    /// every member — the clone only happens for members that actually reach
+    /// a tool/resource handler, not for `initialize`/`tools/list`/notifications.
+    ///
+    /// Per the JSON-RPC 2.0 spec: an empty batch is itself an invalid
+    /// request, returned as a single error (not an empty array); members
+    /// that are notifications are omitted from the result.
+    pub async fn handle_batch(&self, reqs: Vec<JsonRpcRequest>, context: Value) -> Vec<McpResponse> {
+        if reqs.is_empty() {
+            return vec![McpResponse::error(
+                None,
+                ERR_CODE_INVALID_REQ,
+                "batch must not be empty",
+            )];
+        }
+
+        let context = Arc::new(context);
+        let calls = reqs
+            .into_iter()
+            .map(|req| self.handle_with_shared_context(req, &context));
+
+        futures::future::join_all(calls)
+            .await
+            .into_iter()
+            .filter(|resp| !resp.is_notification())
+            .collect()
+    }
+
+    /// Dispatch a [`JsonRpcMessage`] deserialized from an inbound HTTP body,
+    /// routing to [`Self::handle`] or [`Self::handle_batch`] depending on
+    /// whether it was a single request or a batch, and wrapping the result
+    /// in a [`BatchResponse`] so callers can serialize either shape (or
+    /// check [`BatchResponse::is_empty`] for a 202-with-no-body) through
+    /// one call regardless of which came in.
+    pub async fn handle_message(&self, msg: JsonRpcMessage, context: Value) -> BatchResponse {
+        match msg {
+            JsonRpcMessage::Single(req) => BatchResponse::Single(self.handle(req, context).await),
+            JsonRpcMessage::Batch(reqs) => BatchResponse::Batch(self.handle_batch(reqs, context).await),
+        }
+    }
+
+    /// Shared routing behind [`Self::handle`] and [`Self::handle_batch`].
+    /// `context` is borrowed from an `Arc` so a batch can share one without
+    /// cloning it for every member — only members that dispatch to a tool
+    /// or resource handler (which own their context) pay the clone.
+    async fn handle_with_shared_context(&self, req: JsonRpcRequest, context: &Arc<Value>) -> McpResponse {
         if req.jsonrpc != "2.0" {
             return McpResponse::error(req.id, ERR_CODE_INVALID_REQ, "jsonrpc must be '2.0'");
         }
 
+        if let Some(store) = &self.revocation_store {
+            if let Some(jti) = context.get("jti").and_then(|v| v.as_str()) {
+                if store.is_revoked(jti) {
+                    return McpResponse::error(req.id, ERR_CODE_UNAUTHORIZED, "unauthorized");
+                }
+            }
+        }
+
         match req.method.as_str() {
             "initialize" => self.handle_initialize(req.id, req.params),
             "ping" => McpResponse::ok(req.id, json!({})),
             "notifications/initialized" | "notifications/cancelled" => McpResponse::notification(),
             "tools/list" => self.handle_tools_list(req.id),
-            "tools/call" => self.handle_tools_call(req.id, req.params, context).await,
+            "tools/call" => self.handle_tools_call(req.id, req.params, (**context).clone()).await,
             "resources/list" => self.handle_resources_list(req.id),
-            "resources/read" => self.handle_resources_read(req.id, req.params, context).await,
+            "resources/read" => self.handle_resources_read(req.id, req.params, (**context).clone()).await,
+            "resources/subscribe" => self.handle_resources_subscribe(req.id, req.params, context),
+            "resources/unsubscribe" => self.handle_resources_unsubscribe(req.id, req.params, context),
             _ => McpResponse::error(
                 req.id,
                 ERR_CODE_NO_METHOD,
@@ -113,9 +370,30 @@ impl Server {
         }
     }
 
+    /// Authenticate `headers` via `authenticator`, then dispatch `req` with
+    /// the resulting claims as context. On authentication failure, returns
+    /// an [`ERR_CODE_UNAUTHORIZED`] response instead of calling `handle`.
+    ///
+    /// Transport-agnostic: pairs with [`crate::axum_ext::auth_middleware`]
+    /// for Axum, or call directly from any other transport that can hand
+    /// you a `http::HeaderMap`.
+    pub async fn handle_authenticated(
+        &self,
+        req: JsonRpcRequest,
+        headers: &http::HeaderMap,
+        authenticator: &dyn Authenticator,
+    ) -> McpResponse {
+        let id = req.id.clone();
+        match authenticator.authenticate(headers).await {
+            Ok(context) => self.handle(req, context).await,
+            Err(_) => McpResponse::error(id, ERR_CODE_UNAUTHORIZED, "unauthorized"),
+        }
+    }
+
     fn handle_initialize(&self, id: Option<Value>, params: Option<Value>) -> McpResponse {
         // Log client info by borrowing directly into the params Value — no
         // deserialization, no clone.
+        let requested_version = params.as_ref().and_then(|p| p.get("protocolVersion")).and_then(|v| v.as_str());
         if let Some(ref params) = params {
             let client_name = params
                 .pointer("/clientInfo/name")
@@ -125,18 +403,26 @@ impl Server {
                 .pointer("/clientInfo/version")
                 .and_then(|v| v.as_str())
                 .unwrap_or("");
-            let protocol_version = params
-                .get("protocolVersion")
-                .and_then(|v| v.as_str());
             tracing::info!(
                 client_name,
                 client_version,
-                protocol_version,
+                protocol_version = requested_version,
                 "initialize"
             );
         }
 
-        McpResponse::cached(id, &self.initialize_result)
+        // Negotiate: echo back the client's requested version if we support
+        // it, otherwise fall back to our newest supported version so the
+        // client can decide whether to proceed.
+        let negotiated = requested_version
+            .filter(|v| self.initialize_results.contains_key(*v))
+            .unwrap_or(&self.newest_protocol_version);
+
+        let raw = self
+            .initialize_results
+            .get(negotiated)
+            .expect("negotiated version is always a key of initialize_results");
+        McpResponse::cached(id, raw)
     }
 
     fn handle_tools_list(&self, id: Option<Value>) -> McpResponse {
@@ -189,6 +475,14 @@ impl Server {
             return McpResponse::error(id, ERR_CODE_BAD_PARAMS, e);
         }
 
+        // Enforce the tool's declared authorization policy, if any, before
+        // the handler ever runs.
+        if let Some(policy) = self.tool_policies.get(&params.name) {
+            if !policy.is_satisfied_by(&context) {
+                return McpResponse::error(id, ERR_CODE_FORBIDDEN, "forbidden");
+            }
+        }
+
         // Find handler (borrow, no clone).
         let handler = match self.tool_handlers.get(&params.name) {
             Some(h) => h,
@@ -201,9 +495,15 @@ impl Server {
             }
         };
 
-        // Execute handler and convert result to Value.
-        let result = match handler.call(args, context).await {
+        // Execute handler and convert result to Value. A `Validation` error
+        // (e.g. from `TypedToolHandler` failing to deserialize `args`) is a
+        // malformed request, not a tool-level failure, so it short-circuits
+        // as a JSON-RPC error rather than an `is_error` tool result.
+        let result = match handler.call(args, context, &self.state).await {
             Ok(r) => r,
+            Err(McpError::Validation(msg)) => {
+                return McpResponse::error(id, ERR_CODE_BAD_PARAMS, msg);
+            }
             Err(e) => error_result(e.to_string()),
         };
 
@@ -263,7 +563,7 @@ impl Server {
 
         // Check for registered handler.
         if let Some(handler) = self.resource_handlers.get(&target.name) {
-            match handler.call(&target.uri, context).await {
+            match handler.call(&target.uri, context, &self.state).await {
                 Ok(content) => {
                     let result = json!({ "contents": [content] });
                     McpResponse::ok(id, result)
@@ -286,6 +586,73 @@ impl Server {
             McpResponse::ok(id, result)
         }
     }
+
+    fn handle_resources_subscribe(
+        &self,
+        id: Option<Value>,
+        params: Option<Value>,
+        context: &Value,
+    ) -> McpResponse {
+        let (connection_id, uri) = match self.resolve_subscription_request(id.clone(), params, context) {
+            Ok(pair) => pair,
+            Err(resp) => return resp,
+        };
+        self.subscriptions.subscribe(&uri, connection_id);
+        McpResponse::ok(id, json!({}))
+    }
+
+    fn handle_resources_unsubscribe(
+        &self,
+        id: Option<Value>,
+        params: Option<Value>,
+        context: &Value,
+    ) -> McpResponse {
+        let (connection_id, uri) = match self.resolve_subscription_request(id.clone(), params, context) {
+            Ok(pair) => pair,
+            Err(resp) => return resp,
+        };
+        self.subscriptions.unsubscribe(&uri, connection_id);
+        McpResponse::ok(id, json!({}))
+    }
+
+    /// Shared validation for `resources/subscribe` / `resources/unsubscribe`:
+    /// the capability must be enabled, `params.uri` must parse, and the
+    /// transport must have put a `connectionId` in `context` (set when it
+    /// calls [`Self::attach_notifier`]).
+    fn resolve_subscription_request<'a>(
+        &self,
+        id: Option<Value>,
+        params: Option<Value>,
+        context: &'a Value,
+    ) -> Result<(&'a str, String), McpResponse> {
+        if !self.subscriptions_enabled {
+            return Err(McpResponse::error(
+                id,
+                ERR_CODE_NO_METHOD,
+                "resource subscriptions are not enabled on this server",
+            ));
+        }
+
+        let params: ResourceSubscribeParams = match params {
+            Some(p) => serde_json::from_value(p).map_err(|e| {
+                McpResponse::error(id.clone(), ERR_CODE_BAD_PARAMS, format!("invalid params: {}", e))
+            })?,
+            None => return Err(McpResponse::error(id, ERR_CODE_BAD_PARAMS, "params required")),
+        };
+
+        let connection_id = context
+            .get("connectionId")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| {
+                McpResponse::error(
+                    id.clone(),
+                    ERR_CODE_INTERNAL,
+                    "no connectionId in context; transport must attach one to support subscriptions",
+                )
+            })?;
+
+        Ok((connection_id, params.uri))
+    }
 }
 
 /// Serialize a Value to a pre-validated `Box<RawValue>`.
@@ -300,6 +667,15 @@ pub struct ServerBuilder {
     resources: Vec<Resource>,
     server_name: Option<String>,
     server_version: Option<String>,
+    revocation_store: Option<Arc<dyn RevocationStore>>,
+    subscriptions_enabled: bool,
+    pending_tool_handlers: Vec<(String, Arc<dyn ToolHandler>)>,
+    /// Additional protocol versions accepted on top of [`PROTOCOL_VERSION`],
+    /// which is always supported. Set via
+    /// [`Self::protocol_versions`].
+    protocol_versions: Vec<String>,
+    /// Server-wide application state. Set via [`Self::with_state`].
+    state: Option<Arc<dyn Any + Send + Sync>>,
 }
 
 impl ServerBuilder {
@@ -351,6 +727,16 @@ impl ServerBuilder {
         self
     }
 
+    /// Accept additional MCP protocol versions on `initialize` beyond
+    /// [`PROTOCOL_VERSION`], which is always supported. `handle_initialize`
+    /// echoes back whichever supported version the client requests; if the
+    /// client asks for something unsupported, the newest supported version
+    /// is returned instead so the client can decide whether to proceed.
+    pub fn protocol_versions(mut self, versions: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.protocol_versions.extend(versions.into_iter().map(Into::into));
+        self
+    }
+
     /// Set server name and version.
     pub fn server_info(mut self, name: impl Into<String>, version: impl Into<String>) -> Self {
         self.server_name = Some(name.into());
@@ -358,23 +744,101 @@ impl ServerBuilder {
         self
     }
 
+    /// Register a [`RevocationStore`] so `handle` rejects any request whose
+    /// `context` carries a revoked `jti` claim.
+    pub fn revocation_store(mut self, store: Arc<dyn RevocationStore>) -> Self {
+        self.revocation_store = Some(store);
+        self
+    }
+
+    /// Register server-wide application state (a DB pool, HTTP client,
+    /// cache, ...), handed to every `ToolHandler`/`ResourceHandler` call as
+    /// `state`. Recover it in a handler with [`State::get`]. Only the most
+    /// recently registered state is kept — calling this twice replaces
+    /// rather than merges.
+    pub fn with_state<T: Any + Send + Sync + 'static>(mut self, state: Arc<T>) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Register a tool whose `inputSchema` is derived from `T` via
+    /// `schemars::schema_for!`, with a handler that receives `T` already
+    /// deserialized instead of a raw `Value` (see [`TypedToolHandler`]).
+    /// Equivalent to a hand-written schema via [`Self::tools_json`] plus a
+    /// [`Server::handle_tool`] call, kept in sync from one Rust type.
+    pub fn typed_tool<T, F, Fut>(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        handler: F,
+    ) -> Self
+    where
+        T: DeserializeOwned + JsonSchema + Send + Sync + 'static,
+        F: Fn(T, Value, State) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<ToolResult, McpError>> + Send + 'static,
+    {
+        let name = name.into();
+        let input_schema = serde_json::to_value(schemars::schema_for!(T)).unwrap_or(json!({}));
+        let schema_meta = loader::parse_schema_meta(&input_schema);
+
+        self.tools.push(Tool {
+            name: name.clone(),
+            description: description.into(),
+            input_schema,
+            schema_meta,
+        });
+        self.pending_tool_handlers
+            .push((name, TypedToolHandler::new(handler)));
+        self
+    }
+
+    /// Accept `resources/subscribe`/`resources/unsubscribe` and advertise
+    /// `capabilities.resources.subscribe: true` in `initialize`.
+    ///
+    /// Delivery requires the transport to call
+    /// [`Server::attach_notifier`](crate::Server::attach_notifier) per
+    /// connection and put that connection's id at `context["connectionId"]`
+    /// on every request — without either, subscribe/unsubscribe calls will
+    /// be rejected or notifications will have nowhere to go.
+    pub fn enable_resource_subscriptions(mut self) -> Self {
+        self.subscriptions_enabled = true;
+        self
+    }
+
     /// Build the server.
     pub fn build(self) -> Server {
         let server_name = self.server_name.unwrap_or_else(|| "mcpserver".into());
         let server_version = self.server_version.unwrap_or_else(|| "1.0.0".into());
 
-        // Pre-serialize cached results once into RawValue (shared via Arc).
-        let initialize_result: Arc<RawValue> = Arc::from(to_raw(&json!({
-            "protocolVersion": PROTOCOL_VERSION,
-            "capabilities": {
-                "tools": {"listChanged": false},
-                "resources": {"subscribe": false, "listChanged": false},
-            },
-            "serverInfo": {
-                "name": server_name,
-                "version": server_version,
-            },
-        })));
+        // Pre-serialize cached results once into RawValue (shared via Arc) —
+        // one per supported protocol version, so `handle_initialize` stays a
+        // zero-copy lookup regardless of which version the client asks for.
+        let mut protocol_versions: Vec<String> = vec![PROTOCOL_VERSION.to_string()];
+        protocol_versions.extend(self.protocol_versions);
+        protocol_versions.sort();
+        protocol_versions.dedup();
+        let newest_protocol_version = protocol_versions
+            .last()
+            .cloned()
+            .expect("protocol_versions always has at least PROTOCOL_VERSION");
+
+        let initialize_results: HashMap<String, Arc<RawValue>> = protocol_versions
+            .into_iter()
+            .map(|version| {
+                let raw: Arc<RawValue> = Arc::from(to_raw(&json!({
+                    "protocolVersion": version,
+                    "capabilities": {
+                        "tools": {"listChanged": false},
+                        "resources": {"subscribe": self.subscriptions_enabled, "listChanged": false},
+                    },
+                    "serverInfo": {
+                        "name": server_name,
+                        "version": server_version,
+                    },
+                })));
+                (version, raw)
+            })
+            .collect();
 
         let tools_list_result: Arc<RawValue> =
             Arc::from(to_raw(&json!({ "tools": self.tools })));
@@ -401,15 +865,28 @@ impl ServerBuilder {
             })
             .collect();
 
-        Server {
+        let mut server = Server {
             tools: tool_map,
             resources: res_map,
             tool_handlers: HashMap::new(),
+            tool_policies: HashMap::new(),
             resource_handlers: HashMap::new(),
-            initialize_result,
+            revocation_store: self.revocation_store,
+            subscriptions_enabled: self.subscriptions_enabled,
+            subscriptions: ResourceSubscriptions::new(),
+            notifiers: dashmap::DashMap::new(),
+            initialize_results,
+            newest_protocol_version,
             tools_list_result,
             resources_list_result,
+            state: State::new(self.state),
+        };
+
+        for (name, handler) in self.pending_tool_handlers {
+            server.tool_handlers.insert(name, handler);
         }
+
+        server
     }
 }
 
@@ -421,7 +898,7 @@ mod tests {
 
     #[async_trait]
     impl ToolHandler for EchoHandler {
-        async fn call(&self, args: Value, _context: Value) -> Result<ToolResult, McpError> {
+        async fn call(&self, args: Value, _context: Value, _state: &State) -> Result<ToolResult, McpError> {
             let msg = args.get("msg").and_then(|v| v.as_str()).unwrap_or("no msg");
             Ok(text_result(format!("echo: {}", msg)))
         }
@@ -491,6 +968,36 @@ mod tests {
         assert_eq!(result["serverInfo"]["name"], "test-server");
     }
 
+    #[tokio::test]
+    async fn test_initialize_echoes_supported_older_version() {
+        let srv = Server::builder()
+            .tools_json(b"[]")
+            .server_info("test-server", "0.1.0")
+            .protocol_versions(["2024-11-05"])
+            .build();
+        let params = json!({"protocolVersion": "2024-11-05", "capabilities": {}});
+        let resp = srv
+            .handle(make_req("initialize", Some(json!(1)), Some(params)), json!({}))
+            .await
+            .into_json_rpc();
+        assert_eq!(resp.result.unwrap()["protocolVersion"], "2024-11-05");
+    }
+
+    #[tokio::test]
+    async fn test_initialize_falls_back_to_newest_for_unsupported_version() {
+        let srv = Server::builder()
+            .tools_json(b"[]")
+            .server_info("test-server", "0.1.0")
+            .protocol_versions(["2024-11-05"])
+            .build();
+        let params = json!({"protocolVersion": "1999-01-01", "capabilities": {}});
+        let resp = srv
+            .handle(make_req("initialize", Some(json!(1)), Some(params)), json!({}))
+            .await
+            .into_json_rpc();
+        assert_eq!(resp.result.unwrap()["protocolVersion"], PROTOCOL_VERSION);
+    }
+
     #[tokio::test]
     async fn test_ping() {
         let srv = test_server();
@@ -561,6 +1068,55 @@ mod tests {
         assert_eq!(resp.error.unwrap().code, ERR_CODE_INTERNAL);
     }
 
+    struct CounterState {
+        count: std::sync::atomic::AtomicUsize,
+    }
+
+    #[tokio::test]
+    async fn test_with_state_reaches_handler() {
+        let state = Arc::new(CounterState { count: std::sync::atomic::AtomicUsize::new(0) });
+        let mut srv = Server::builder()
+            .tools_json(
+                r#"[{"name":"bump","description":"bumps a shared counter","inputSchema":{"type":"object","properties":{}}}]"#
+                    .as_bytes(),
+            )
+            .with_state(state.clone())
+            .build();
+
+        srv.handle_tool(
+            "bump",
+            FnToolHandler::new(|_args: Value, _context: Value, state: State| async move {
+                let counter = state.get::<CounterState>().expect("state registered");
+                let n = counter.count.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+                Ok(text_result(n.to_string()))
+            }),
+        );
+
+        let params = json!({"name": "bump", "arguments": {}});
+        let resp = srv
+            .handle(make_req("tools/call", Some(json!(1)), Some(params)), json!({}))
+            .await
+            .into_json_rpc();
+        let result: ToolResult = serde_json::from_value(resp.result.unwrap()).unwrap();
+        assert_eq!(result.content[0].text.as_deref(), Some("1"));
+        assert_eq!(state.count.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_state_get_wrong_type_is_none() {
+        let state = State::new(Some(Arc::new(CounterState {
+            count: std::sync::atomic::AtomicUsize::new(0),
+        })));
+        assert!(state.get::<CounterState>().is_some());
+        assert!(state.get::<String>().is_none());
+    }
+
+    #[test]
+    fn test_state_default_is_none() {
+        let state = State::default();
+        assert!(state.get::<CounterState>().is_none());
+    }
+
     #[tokio::test]
     async fn test_resources_list() {
         let srv = test_server();
@@ -607,6 +1163,91 @@ mod tests {
         assert!(resp.error.is_some());
     }
 
+    #[tokio::test]
+    async fn test_tools_call_policy_forbidden() {
+        let mut srv = test_server();
+        srv.handle_tool_with_policy(
+            "echo",
+            Arc::new(EchoHandler),
+            ToolPolicy::AnyGroup(vec!["admins".into()]),
+        );
+
+        let params = json!({"name": "echo", "arguments": {"msg": "hi"}});
+        let context = json!({"cognito:groups": ["users"]});
+        let resp = srv
+            .handle(make_req("tools/call", Some(json!(1)), Some(params)), context)
+            .await
+            .into_json_rpc();
+        assert_eq!(resp.error.unwrap().code, ERR_CODE_FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_tools_call_policy_allowed() {
+        let mut srv = test_server();
+        srv.handle_tool_with_policy(
+            "echo",
+            Arc::new(EchoHandler),
+            ToolPolicy::AnyScope(vec!["echo:write".into()]),
+        );
+
+        let params = json!({"name": "echo", "arguments": {"msg": "hi"}});
+        let context = json!({"scope": "openid echo:write profile"});
+        let resp = srv
+            .handle(make_req("tools/call", Some(json!(1)), Some(params)), context)
+            .await
+            .into_json_rpc();
+        assert!(resp.error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_revoked_jti_rejected() {
+        use crate::revocation::InMemoryRevocationStore;
+
+        let store = Arc::new(InMemoryRevocationStore::new());
+        store.revoke("revoked-jti", u64::MAX);
+
+        let mut srv = Server::builder()
+            .tools_json(
+                r#"[{"name":"echo","description":"echoes","inputSchema":{"type":"object","properties":{"msg":{"type":"string"}},"required":["msg"]}}]"#
+                    .as_bytes(),
+            )
+            .revocation_store(store)
+            .build();
+        srv.handle_tool("echo", Arc::new(EchoHandler));
+
+        let params = json!({"name": "echo", "arguments": {"msg": "hi"}});
+        let context = json!({"jti": "revoked-jti"});
+        let resp = srv
+            .handle(make_req("tools/call", Some(json!(1)), Some(params)), context)
+            .await
+            .into_json_rpc();
+        assert_eq!(resp.error.unwrap().code, ERR_CODE_UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_unrevoked_jti_allowed() {
+        use crate::revocation::InMemoryRevocationStore;
+
+        let store = Arc::new(InMemoryRevocationStore::new());
+
+        let mut srv = Server::builder()
+            .tools_json(
+                r#"[{"name":"echo","description":"echoes","inputSchema":{"type":"object","properties":{"msg":{"type":"string"}},"required":["msg"]}}]"#
+                    .as_bytes(),
+            )
+            .revocation_store(store)
+            .build();
+        srv.handle_tool("echo", Arc::new(EchoHandler));
+
+        let params = json!({"name": "echo", "arguments": {"msg": "hi"}});
+        let context = json!({"jti": "still-valid"});
+        let resp = srv
+            .handle(make_req("tools/call", Some(json!(1)), Some(params)), context)
+            .await
+            .into_json_rpc();
+        assert!(resp.error.is_none());
+    }
+
     /// Verify that serializing an McpResponse produces valid JSON-RPC.
     #[tokio::test]
     async fn test_serialize_cached_response() {
@@ -619,4 +1260,274 @@ mod tests {
         let tools = parsed.result.unwrap()["tools"].as_array().unwrap().len();
         assert_eq!(tools, 1);
     }
+
+    #[tokio::test]
+    async fn test_handle_batch_empty_is_invalid_request() {
+        let srv = test_server();
+        let results = srv.handle_batch(vec![], json!({})).await;
+        assert_eq!(results.len(), 1);
+        let resp = results.into_iter().next().unwrap().into_json_rpc();
+        assert_eq!(resp.error.unwrap().code, ERR_CODE_INVALID_REQ);
+    }
+
+    #[tokio::test]
+    async fn test_handle_batch_omits_notifications() {
+        let srv = test_server();
+        let reqs = vec![
+            make_req("ping", Some(json!(1)), None),
+            make_req("notifications/initialized", None, None),
+            make_req("ping", Some(json!(2)), None),
+        ];
+        let results = srv.handle_batch(reqs, json!({})).await;
+        assert_eq!(results.len(), 2);
+        assert!(!results.iter().any(|r| r.is_notification()));
+    }
+
+    #[tokio::test]
+    async fn test_handle_batch_all_notifications_returns_empty() {
+        let srv = test_server();
+        let reqs = vec![
+            make_req("notifications/initialized", None, None),
+            make_req("notifications/cancelled", None, None),
+        ];
+        let results = srv.handle_batch(reqs, json!({})).await;
+        assert!(results.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_handle_batch_preserves_order_and_ids() {
+        let srv = test_server();
+        let params = json!({"name": "echo", "arguments": {"msg": "hi"}});
+        let reqs = vec![
+            make_req("ping", Some(json!("a")), None),
+            make_req("tools/call", Some(json!("b")), Some(params)),
+        ];
+        let results = srv.handle_batch(reqs, json!({})).await;
+        let responses: Vec<_> = results.into_iter().map(|r| r.into_json_rpc()).collect();
+        assert_eq!(responses[0].id, Some(json!("a")));
+        assert_eq!(responses[1].id, Some(json!("b")));
+    }
+
+    #[tokio::test]
+    async fn test_handle_message_single_is_not_array() {
+        let srv = test_server();
+        let msg: JsonRpcMessage = serde_json::from_value(json!({
+            "jsonrpc": "2.0", "id": 1, "method": "ping"
+        })).unwrap();
+        let resp = srv.handle_message(msg, json!({})).await;
+        assert!(!resp.is_empty());
+        let value = serde_json::to_value(&resp).unwrap();
+        assert!(value.is_object());
+    }
+
+    #[tokio::test]
+    async fn test_handle_message_batch_serializes_as_array() {
+        let srv = test_server();
+        let msg: JsonRpcMessage = serde_json::from_value(json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "ping"},
+            {"jsonrpc": "2.0", "id": 2, "method": "ping"},
+        ])).unwrap();
+        let resp = srv.handle_message(msg, json!({})).await;
+        assert!(!resp.is_empty());
+        let value = serde_json::to_value(&resp).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_handle_message_all_notifications_batch_is_empty() {
+        let srv = test_server();
+        let msg: JsonRpcMessage = serde_json::from_value(json!([
+            {"jsonrpc": "2.0", "method": "notifications/initialized"},
+            {"jsonrpc": "2.0", "method": "notifications/cancelled"},
+        ])).unwrap();
+        let resp = srv.handle_message(msg, json!({})).await;
+        assert!(resp.is_empty());
+    }
+
+    struct RecordingNotifier {
+        sent: std::sync::Mutex<Vec<(String, Value)>>,
+    }
+
+    impl RecordingNotifier {
+        fn new() -> Self {
+            RecordingNotifier { sent: std::sync::Mutex::new(Vec::new()) }
+        }
+    }
+
+    #[async_trait]
+    impl Notifier for RecordingNotifier {
+        async fn notify(&self, method: &str, params: Value) {
+            self.sent.lock().unwrap().push((method.to_string(), params));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resources_subscribe_not_enabled() {
+        let srv = test_server();
+        let params = json!({"uri": "file:///test.csv"});
+        let context = json!({"connectionId": "conn-1"});
+        let resp = srv
+            .handle(make_req("resources/subscribe", Some(json!(1)), Some(params)), context)
+            .await
+            .into_json_rpc();
+        assert_eq!(resp.error.unwrap().code, ERR_CODE_NO_METHOD);
+    }
+
+    fn subscribable_test_server() -> Server {
+        let tools_json = r#"[
+            {"name":"echo","description":"echoes","inputSchema":{"type":"object","properties":{"msg":{"type":"string"}},"required":["msg"]}}
+        ]"#;
+        let resources_json = r#"[
+            {"name":"test","description":"test resource","uri":"file:///test.csv","mimeType":"text/csv"}
+        ]"#;
+
+        let mut srv = Server::builder()
+            .tools_json(tools_json.as_bytes())
+            .resources_json(resources_json.as_bytes())
+            .server_info("test-server", "0.1.0")
+            .enable_resource_subscriptions()
+            .build();
+
+        srv.handle_tool("echo", Arc::new(EchoHandler));
+        srv
+    }
+
+    #[tokio::test]
+    async fn test_initialize_advertises_subscribe_when_enabled() {
+        let srv = subscribable_test_server();
+        let resp = srv
+            .handle(make_req("initialize", Some(json!(1)), None), json!({}))
+            .await
+            .into_json_rpc();
+        let result = resp.result.unwrap();
+        assert_eq!(result["capabilities"]["resources"]["subscribe"], true);
+    }
+
+    #[tokio::test]
+    async fn test_resources_subscribe_requires_connection_id() {
+        let srv = subscribable_test_server();
+        let params = json!({"uri": "file:///test.csv"});
+        let resp = srv
+            .handle(make_req("resources/subscribe", Some(json!(1)), Some(params)), json!({}))
+            .await
+            .into_json_rpc();
+        assert_eq!(resp.error.unwrap().code, ERR_CODE_INTERNAL);
+    }
+
+    #[tokio::test]
+    async fn test_resources_subscribe_and_notify() {
+        let srv = subscribable_test_server();
+        let notifier = Arc::new(RecordingNotifier::new());
+        srv.attach_notifier("conn-1", notifier.clone());
+
+        let params = json!({"uri": "file:///test.csv"});
+        let context = json!({"connectionId": "conn-1"});
+        let resp = srv
+            .handle(make_req("resources/subscribe", Some(json!(1)), Some(params)), context)
+            .await
+            .into_json_rpc();
+        assert!(resp.error.is_none());
+
+        srv.notify_resource_updated("file:///test.csv").await;
+
+        let sent = notifier.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, "notifications/resources/updated");
+        assert_eq!(sent[0].1["uri"], "file:///test.csv");
+    }
+
+    #[tokio::test]
+    async fn test_resources_unsubscribe_stops_delivery() {
+        let srv = subscribable_test_server();
+        let notifier = Arc::new(RecordingNotifier::new());
+        srv.attach_notifier("conn-1", notifier.clone());
+
+        let context = json!({"connectionId": "conn-1"});
+        let params = json!({"uri": "file:///test.csv"});
+        srv.handle(make_req("resources/subscribe", Some(json!(1)), Some(params.clone())), context.clone())
+            .await;
+        srv.handle(make_req("resources/unsubscribe", Some(json!(2)), Some(params)), context)
+            .await;
+
+        srv.notify_resource_updated("file:///test.csv").await;
+        assert!(notifier.sent.lock().unwrap().is_empty());
+    }
+
+    #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+    struct GreetArgs {
+        name: String,
+    }
+
+    #[tokio::test]
+    async fn test_typed_tool_success() {
+        let srv = Server::builder()
+            .server_info("test-server", "0.1.0")
+            .typed_tool::<GreetArgs, _, _>("greet", "says hello", |args: GreetArgs, _ctx, _state| async move {
+                Ok(text_result(format!("hello, {}", args.name)))
+            })
+            .build();
+
+        let params = json!({"name": "greet", "arguments": {"name": "ada"}});
+        let resp = srv
+            .handle(make_req("tools/call", Some(json!(1)), Some(params)), json!({}))
+            .await
+            .into_json_rpc();
+        assert!(resp.error.is_none());
+        let result: ToolResult = serde_json::from_value(resp.result.unwrap()).unwrap();
+        assert_eq!(result.content[0].text.as_deref(), Some("hello, ada"));
+    }
+
+    #[tokio::test]
+    async fn test_typed_tool_derives_schema() {
+        let srv = Server::builder()
+            .server_info("test-server", "0.1.0")
+            .typed_tool::<GreetArgs, _, _>("greet", "says hello", |args: GreetArgs, _ctx, _state| async move {
+                Ok(text_result(args.name))
+            })
+            .build();
+
+        let resp = srv
+            .handle(make_req("tools/list", Some(json!(1)), None), json!({}))
+            .await
+            .into_json_rpc();
+        let tools = resp.result.unwrap()["tools"].clone();
+        assert_eq!(tools[0]["name"], "greet");
+        assert_eq!(tools[0]["inputSchema"]["required"], json!(["name"]));
+    }
+
+    #[tokio::test]
+    async fn test_typed_tool_deserialize_failure_is_bad_params() {
+        let srv = Server::builder()
+            .server_info("test-server", "0.1.0")
+            .typed_tool::<GreetArgs, _, _>("greet", "says hello", |args: GreetArgs, _ctx, _state| async move {
+                Ok(text_result(args.name))
+            })
+            .build();
+
+        // Passes schema-level required-field validation (the key is present)
+        // but fails `GreetArgs` deserialization (wrong type) — must surface
+        // as ERR_CODE_BAD_PARAMS rather than a tool-level `is_error` result.
+        let params = json!({"name": "greet", "arguments": {"name": 5}});
+        let resp = srv
+            .handle(make_req("tools/call", Some(json!(1)), Some(params)), json!({}))
+            .await
+            .into_json_rpc();
+        assert_eq!(resp.error.unwrap().code, ERR_CODE_BAD_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_detach_notifier_drops_subscriptions() {
+        let srv = subscribable_test_server();
+        let notifier = Arc::new(RecordingNotifier::new());
+        srv.attach_notifier("conn-1", notifier.clone());
+
+        let context = json!({"connectionId": "conn-1"});
+        let params = json!({"uri": "file:///test.csv"});
+        srv.handle(make_req("resources/subscribe", Some(json!(1)), Some(params)), context)
+            .await;
+
+        srv.detach_notifier("conn-1");
+        srv.notify_resource_updated("file:///test.csv").await;
+        assert!(notifier.sent.lock().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,100 @@
+//! Resource-change subscriptions and the outbound channel that delivers
+//! them.
+//!
+//! [`Server::handle`](crate::Server::handle) is one-shot request/response,
+//! so pushing a `notifications/resources/updated` message to a subscribed
+//! client needs an outbound sink attached from the transport layer — a
+//! [`Notifier`] registered per connection via
+//! [`Server::attach_notifier`](crate::Server::attach_notifier). Subscribing
+//! is `resources/subscribe`/`resources/unsubscribe` over the normal
+//! request/response path; delivery goes out through whichever `Notifier`
+//! is attached for that subscriber's connection.
+
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use serde_json::Value;
+
+/// An outbound sink a transport attaches so the server can push
+/// server-initiated JSON-RPC notifications (e.g. onto an SSE or WebSocket
+/// stream) instead of only ever replying to a request.
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    /// Send `method`/`params` as a JSON-RPC notification.
+    async fn notify(&self, method: &str, params: Value);
+}
+
+/// Per-resource-URI subscriber sets, keyed by the connection id the
+/// transport assigns (e.g. a session id threaded through `context`).
+#[derive(Default)]
+pub struct ResourceSubscriptions {
+    by_uri: DashMap<String, HashSet<String>>,
+}
+
+impl ResourceSubscriptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe(&self, uri: &str, connection_id: &str) {
+        self.by_uri
+            .entry(uri.to_string())
+            .or_default()
+            .insert(connection_id.to_string());
+    }
+
+    pub fn unsubscribe(&self, uri: &str, connection_id: &str) {
+        if let Some(mut subscribers) = self.by_uri.get_mut(uri) {
+            subscribers.remove(connection_id);
+        }
+    }
+
+    /// Drop `connection_id` from every resource it was subscribed to. Call
+    /// this on disconnect so subscriptions don't outlive the connection.
+    pub fn unsubscribe_all(&self, connection_id: &str) {
+        for mut subscribers in self.by_uri.iter_mut() {
+            subscribers.remove(connection_id);
+        }
+    }
+
+    pub fn subscribers(&self, uri: &str) -> Vec<String> {
+        self.by_uri
+            .get(uri)
+            .map(|subscribers| subscribers.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscribe_and_list() {
+        let subs = ResourceSubscriptions::new();
+        subs.subscribe("file:///a.csv", "conn-1");
+        subs.subscribe("file:///a.csv", "conn-2");
+        let mut subscribers = subs.subscribers("file:///a.csv");
+        subscribers.sort();
+        assert_eq!(subscribers, vec!["conn-1", "conn-2"]);
+    }
+
+    #[test]
+    fn test_unsubscribe() {
+        let subs = ResourceSubscriptions::new();
+        subs.subscribe("file:///a.csv", "conn-1");
+        subs.unsubscribe("file:///a.csv", "conn-1");
+        assert!(subs.subscribers("file:///a.csv").is_empty());
+    }
+
+    #[test]
+    fn test_unsubscribe_all_drops_every_resource() {
+        let subs = ResourceSubscriptions::new();
+        subs.subscribe("file:///a.csv", "conn-1");
+        subs.subscribe("file:///b.csv", "conn-1");
+        subs.unsubscribe_all("conn-1");
+        assert!(subs.subscribers("file:///a.csv").is_empty());
+        assert!(subs.subscribers("file:///b.csv").is_empty());
+    }
+}
@@ -12,6 +12,13 @@ pub const ERR_CODE_NO_METHOD: i32 = -32601;
 pub const ERR_CODE_BAD_PARAMS: i32 = -32602;
 pub const ERR_CODE_INTERNAL: i32 = -32603;
 
+/// MCP-specific error code: the caller's context does not satisfy the
+/// tool's declared [`crate::server::ToolPolicy`].
+pub const ERR_CODE_FORBIDDEN: i32 = -32001;
+
+/// MCP-specific error code: the request's `jti` claim has been revoked.
+pub const ERR_CODE_UNAUTHORIZED: i32 = -32002;
+
 /// MCP Protocol version this server implements.
 pub const PROTOCOL_VERSION: &str = "2025-03-26";
 
@@ -28,6 +35,19 @@ pub struct JsonRpcRequest {
     pub params: Option<Value>,
 }
 
+/// Deserialization entry point for an inbound HTTP body that may be either
+/// a single JSON-RPC request object or a JSON-RPC 2.0 batch (an array of
+/// them). Deserialize the raw body into this, then pass it to
+/// [`Server::handle_message`](crate::Server::handle_message), which routes
+/// to [`Server::handle`](crate::Server::handle) or
+/// [`Server::handle_batch`](crate::Server::handle_batch) as appropriate.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum JsonRpcMessage {
+    Batch(Vec<JsonRpcRequest>),
+    Single(JsonRpcRequest),
+}
+
 // ── Response ──
 
 /// Response from [`Server::handle()`](crate::Server::handle).
@@ -158,6 +178,41 @@ impl Serialize for McpResponse {
     }
 }
 
+/// Result of dispatching a [`JsonRpcMessage`] via
+/// [`Server::handle_message`](crate::Server::handle_message): a lone
+/// [`McpResponse`] for [`JsonRpcMessage::Single`], or a `Vec` for
+/// [`JsonRpcMessage::Batch`] that serializes as a JSON array.
+///
+/// An all-notification batch serializes as `[]`, which is valid JSON-RPC
+/// but not what you want to send — per spec, that case should get an empty
+/// HTTP body (e.g. a bare 202) instead. Check [`BatchResponse::is_empty`]
+/// before serializing to tell the two apart.
+#[derive(Debug)]
+pub enum BatchResponse {
+    Single(McpResponse),
+    Batch(Vec<McpResponse>),
+}
+
+impl BatchResponse {
+    /// True when there is no response body to send: a lone notification,
+    /// or a batch where every member was one.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            BatchResponse::Single(resp) => resp.is_notification(),
+            BatchResponse::Batch(resps) => resps.is_empty(),
+        }
+    }
+}
+
+impl Serialize for BatchResponse {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BatchResponse::Single(resp) => resp.serialize(serializer),
+            BatchResponse::Batch(resps) => resps.serialize(serializer),
+        }
+    }
+}
+
 // ── Legacy structured response (kept for deserialization / test inspection) ──
 
 /// Structured JSON-RPC 2.0 response.
@@ -350,3 +405,8 @@ pub(crate) struct ResourceReadParams {
     #[serde(default)]
     pub uri: Option<String>,
 }
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct ResourceSubscribeParams {
+    pub uri: String,
+}